@@ -0,0 +1,180 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::manifest::{
+    EnumInfo, EnumVariantInfo, FieldInfo, FileInfo, FunctionInfo, ParamInfo, PropertyInfo,
+    StructInfo, TypeInfo, TypeVariant,
+};
+
+/// Builds a `FileInfo` programmatically, e.g. for generator tests, such as
+/// `FileInfoBuilder::new("TWFoo").add_class("TWFoo").build()`. All items are
+/// public and kept in the order they are added.
+#[derive(Debug, Clone)]
+pub struct FileInfoBuilder {
+    file: FileInfo,
+}
+
+impl FileInfoBuilder {
+    pub fn new(name: &str) -> Self {
+        FileInfoBuilder {
+            file: FileInfo {
+                name: name.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Adds a struct with the given fields, in declaration order.
+    pub fn add_struct(mut self, name: &str, fields: Vec<(&str, TypeInfo)>) -> Self {
+        self.file.structs.push(StructInfo {
+            name: name.to_string(),
+            is_public: true,
+            is_class: false,
+            is_deprecated: false,
+            deprecation_note: None,
+            fields: fields
+                .into_iter()
+                .map(|(name, ty)| FieldInfo {
+                    name: name.to_string(),
+                    ty,
+                    bit_width: None,
+                    comments: vec![],
+                })
+                .collect(),
+            defined_in: None,
+        });
+        self
+    }
+
+    /// Adds an opaque class, such as `TWPrivateKey`.
+    pub fn add_class(mut self, name: &str) -> Self {
+        self.file.structs.push(StructInfo {
+            name: name.to_string(),
+            is_public: true,
+            is_class: true,
+            is_deprecated: false,
+            deprecation_note: None,
+            fields: vec![],
+            defined_in: None,
+        });
+        self
+    }
+
+    /// Adds a `uint32_t` based enum with the given variant names and values.
+    pub fn add_enum(mut self, name: &str, variants: &[(&str, u32)]) -> Self {
+        self.file.enums.push(EnumInfo {
+            name: name.to_string(),
+            is_public: true,
+            value_type: TypeVariant::UInt32T,
+            variants: variants
+                .iter()
+                .map(|(name, value)| EnumVariantInfo {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                    as_string: None,
+                    comments: vec![],
+                })
+                .collect(),
+            defined_in: None,
+        });
+        self
+    }
+
+    /// Adds a method, whose first parameter is the instance.
+    pub fn add_method(
+        self,
+        name: &str,
+        params: Vec<(&str, TypeInfo)>,
+        return_type: TypeInfo,
+    ) -> Self {
+        self.add_function(name, false, params, return_type)
+    }
+
+    pub fn add_static_method(
+        self,
+        name: &str,
+        params: Vec<(&str, TypeInfo)>,
+        return_type: TypeInfo,
+    ) -> Self {
+        self.add_function(name, true, params, return_type)
+    }
+
+    pub fn add_property(mut self, name: &str, return_type: TypeInfo) -> Self {
+        self.file.properties.push(PropertyInfo {
+            name: name.to_string(),
+            is_public: true,
+            return_type,
+            ownership: None,
+            comments: vec![],
+        });
+        self
+    }
+
+    /// Associates each function with its owning type, like `parse_str` does.
+    pub fn build(mut self) -> FileInfo {
+        let associated = self
+            .file
+            .functions
+            .iter()
+            .map(|func| self.file.associated_type(&func.name).map(str::to_string))
+            .collect::<Vec<_>>();
+
+        for (func, associated) in self.file.functions.iter_mut().zip(associated) {
+            func.associated_type = associated;
+        }
+
+        self.file
+    }
+
+    fn add_function(
+        mut self,
+        name: &str,
+        is_static: bool,
+        params: Vec<(&str, TypeInfo)>,
+        return_type: TypeInfo,
+    ) -> Self {
+        self.file.functions.push(FunctionInfo {
+            name: name.to_string(),
+            is_public: true,
+            is_static,
+            associated_type: None,
+            is_deprecated: false,
+            deprecation_note: None,
+            params: params
+                .into_iter()
+                .map(|(name, ty)| ParamInfo {
+                    name: name.to_string(),
+                    ty,
+                })
+                .collect(),
+            is_variadic: false,
+            return_type,
+            ownership: None,
+            comments: vec![],
+            defined_in: None,
+        });
+        self
+    }
+}
+
+/// A non-pointer type, e.g. `bool`.
+pub fn value(variant: TypeVariant) -> TypeInfo {
+    TypeInfo {
+        variant,
+        is_constant: false,
+        is_nullable: false,
+        pointer_depth: 0,
+        is_pointer_const: false,
+    }
+}
+
+/// A non-nullable pointer, e.g. `TWString* _Nonnull`.
+pub fn pointer(variant: TypeVariant) -> TypeInfo {
+    TypeInfo {
+        pointer_depth: 1,
+        ..value(variant)
+    }
+}
@@ -0,0 +1,356 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::{c_functions, reject_bitfields, TargetLanguage};
+use crate::manifest::{EnumInfo, FileInfo, ParamInfo, StructInfo, TypeInfo, TypeVariant};
+use crate::Result;
+use handlebars::Handlebars;
+use heck::ToUpperCamelCase;
+use std::fmt::Display;
+
+const FILE_TEMPLATE: &str = include_str!("templates/file.hbs");
+const STRING_HELPER_TEMPLATE: &str = include_str!("templates/string_helper.hbs");
+
+/// Represents a sequential struct, for structs with fields. Opaque structs
+/// are passed on as `IntPtr` handles instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsharpStruct {
+    name: String,
+    fields: Vec<CsharpParam>,
+}
+
+/// Represents a C# enum with an explicit underlying type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsharpEnum {
+    name: String,
+    value_type: CsharpType,
+    variants: Vec<CsharpEnumVariant>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsharpEnumVariant {
+    name: String,
+    value: String,
+}
+
+/// Represents a `[DllImport]` declaration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsharpFunction {
+    name: String,
+    params: Vec<CsharpParam>,
+    // `None` if the function returns `void`.
+    #[serde(rename = "return")]
+    return_type: Option<CsharpReturn>,
+}
+
+/// Represents a function parameter or a struct field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsharpParam {
+    name: String,
+    #[serde(rename = "type")]
+    param_type: CsharpType,
+    // The `MarshalAs` attribute, e.g. `UnmanagedType.I1` for booleans.
+    marshal: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsharpReturn {
+    #[serde(rename = "type")]
+    param_type: CsharpType,
+    marshal: Option<String>,
+    // Nullable pointers are returned as `IntPtr.Zero`.
+    is_nullable: bool,
+}
+
+// Wrapper around a valid C# type (built in or custom). Meant to be used as
+// `<CsharpType as From<&TypeInfo>>::from(...)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsharpType(String);
+
+impl Display for CsharpType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Convert the `TypeVariant` into the appropriate C# type, for values which
+/// are passed on directly.
+impl From<TypeVariant> for CsharpType {
+    fn from(value: TypeVariant) -> Self {
+        let res = match value {
+            TypeVariant::Void => "void".to_string(),
+            TypeVariant::Bool => "bool".to_string(),
+            TypeVariant::Char | TypeVariant::Int8T => "sbyte".to_string(),
+            TypeVariant::ShortInt | TypeVariant::Int16T => "short".to_string(),
+            TypeVariant::Int | TypeVariant::Int32T => "int".to_string(),
+            TypeVariant::UnsignedInt | TypeVariant::UInt32T => "uint".to_string(),
+            TypeVariant::LongInt | TypeVariant::Int64T => "long".to_string(),
+            TypeVariant::UnsignedLongInt => "ulong".to_string(),
+            TypeVariant::Float => "float".to_string(),
+            TypeVariant::Double => "double".to_string(),
+            TypeVariant::SizeT => "UIntPtr".to_string(),
+            TypeVariant::IntPtr => "IntPtr".to_string(),
+            TypeVariant::UInt8T => "byte".to_string(),
+            TypeVariant::UInt16T => "ushort".to_string(),
+            TypeVariant::UInt64T => "ulong".to_string(),
+            TypeVariant::CString => "string".to_string(),
+            TypeVariant::Array { element, .. } => format!("{}[]", CsharpType::from(*element)),
+            // Strings, buffers and callbacks are native handles.
+            TypeVariant::String | TypeVariant::Data | TypeVariant::FunctionPointer { .. } => {
+                "IntPtr".to_string()
+            }
+            TypeVariant::Unknown(raw) => raw,
+            // Keep the C names, which do not collide with the bindings on top.
+            TypeVariant::Struct(n) | TypeVariant::Enum(n) => n,
+        };
+
+        CsharpType(res)
+    }
+}
+
+impl From<&TypeInfo> for CsharpType {
+    fn from(ty: &TypeInfo) -> Self {
+        // All pointers are passed on as native handles.
+        if ty.is_pointer() {
+            return CsharpType("IntPtr".to_string());
+        }
+
+        CsharpType::from(ty.variant.clone())
+    }
+}
+
+impl From<&ParamInfo> for CsharpParam {
+    fn from(param: &ParamInfo) -> Self {
+        CsharpParam {
+            name: param_name(&param.name),
+            param_type: CsharpType::from(&param.ty),
+            marshal: marshal(&param.ty),
+        }
+    }
+}
+
+impl From<&TypeInfo> for CsharpReturn {
+    fn from(ty: &TypeInfo) -> Self {
+        CsharpReturn {
+            param_type: CsharpType::from(ty),
+            marshal: marshal(ty),
+            is_nullable: ty.is_nullable && ty.is_pointer(),
+        }
+    }
+}
+
+/// Generates the C# P/Invoke declarations for all public structs, enums and
+/// functions of the given file. The functions are declared in a static
+/// class named after the file, e.g. `MnemonicNative` for `TWMnemonic`.
+pub fn generate_csharp(file: &FileInfo) -> Result<String> {
+    let mut engine = Handlebars::new();
+    // Unmatched variables should result in an error.
+    engine.set_strict_mode(true);
+    // Generic types must not be HTML-escaped.
+    engine.register_escape_fn(handlebars::no_escape);
+
+    engine.register_template_string("file", FILE_TEMPLATE)?;
+
+    // Opaque structs are only referred to by `IntPtr` handles.
+    let structs = file
+        .structs
+        .iter()
+        .filter(|strct| strct.is_public && !strct.fields.is_empty())
+        .map(process_struct)
+        .collect::<Result<Vec<_>>>()?;
+
+    let enums = file
+        .enums
+        .iter()
+        .filter(|enm| enm.is_public)
+        .map(process_enum)
+        .collect::<Vec<_>>();
+
+    // Variadic functions cannot be declared via `[DllImport]`.
+    let functions = c_functions(file)
+        .into_iter()
+        .filter(|func| !func.is_variadic)
+        .map(|func| CsharpFunction {
+            name: func.name,
+            params: func.params.iter().map(CsharpParam::from).collect(),
+            return_type: func.return_type.as_ref().map(CsharpReturn::from),
+        })
+        .collect::<Vec<_>>();
+
+    let class_name = format!(
+        "{}Native",
+        file.name.strip_prefix("TW").unwrap_or(&file.name)
+    );
+
+    let out = engine.render(
+        "file",
+        &serde_json::json!({
+            "current_year": crate::current_year(),
+            "class_name": class_name,
+            "enums": enums,
+            "structs": structs,
+            "functions": functions,
+        }),
+    )?;
+
+    Ok(out)
+}
+
+/// Generates the `TWStringHelper` class, which converts `TWString` handles
+/// from and to managed strings. Shared by all generated files.
+pub fn generate_csharp_string_helper() -> Result<String> {
+    let mut engine = Handlebars::new();
+    engine.set_strict_mode(true);
+    engine.register_escape_fn(handlebars::no_escape);
+
+    engine.register_template_string("string_helper", STRING_HELPER_TEMPLATE)?;
+
+    let out = engine.render(
+        "string_helper",
+        &serde_json::json!({
+            "current_year": crate::current_year(),
+        }),
+    )?;
+
+    Ok(out)
+}
+
+fn process_struct(strct: &StructInfo) -> Result<CsharpStruct> {
+    reject_bitfields(strct, TargetLanguage::CSharp)?;
+
+    Ok(CsharpStruct {
+        name: strct.name.clone(),
+        fields: strct
+            .fields
+            .iter()
+            .map(|field| CsharpParam {
+                name: param_name(&field.name),
+                param_type: CsharpType::from(&field.ty),
+                marshal: marshal(&field.ty),
+            })
+            .collect(),
+    })
+}
+
+fn process_enum(enm: &EnumInfo) -> CsharpEnum {
+    CsharpEnum {
+        name: enm.name.clone(),
+        value_type: CsharpType::from(enm.value_type.clone()),
+        variants: enm
+            .variants
+            .iter()
+            .map(|variant| CsharpEnumVariant {
+                name: variant.name.to_upper_camel_case(),
+                value: variant.value.clone(),
+            })
+            .collect(),
+    }
+}
+
+// Returns the `MarshalAs` attribute required for the type, if any. C booleans
+// are a single byte, while C# marshals `bool` as four bytes by default.
+fn marshal(ty: &TypeInfo) -> Option<String> {
+    if ty.is_pointer() {
+        return None;
+    }
+
+    match &ty.variant {
+        TypeVariant::Bool => Some("UnmanagedType.I1".to_string()),
+        TypeVariant::Array { len: Some(len), .. } => {
+            Some(format!("UnmanagedType.ByValArray, SizeConst = {len}"))
+        }
+        _ => None,
+    }
+}
+
+// Escapes parameter names which are C# keywords, such as `string`.
+fn param_name(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract",
+        "as",
+        "base",
+        "bool",
+        "break",
+        "byte",
+        "case",
+        "catch",
+        "char",
+        "checked",
+        "class",
+        "const",
+        "continue",
+        "decimal",
+        "default",
+        "delegate",
+        "do",
+        "double",
+        "else",
+        "enum",
+        "event",
+        "explicit",
+        "extern",
+        "false",
+        "finally",
+        "fixed",
+        "float",
+        "for",
+        "foreach",
+        "goto",
+        "if",
+        "implicit",
+        "in",
+        "int",
+        "interface",
+        "internal",
+        "is",
+        "lock",
+        "long",
+        "namespace",
+        "new",
+        "null",
+        "object",
+        "operator",
+        "out",
+        "override",
+        "params",
+        "private",
+        "protected",
+        "public",
+        "readonly",
+        "ref",
+        "return",
+        "sbyte",
+        "sealed",
+        "short",
+        "sizeof",
+        "stackalloc",
+        "static",
+        "string",
+        "struct",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "typeof",
+        "uint",
+        "ulong",
+        "unchecked",
+        "unsafe",
+        "ushort",
+        "using",
+        "virtual",
+        "void",
+        "volatile",
+        "while",
+    ];
+
+    if KEYWORDS.contains(&name) {
+        format!("@{name}")
+    } else {
+        name.to_string()
+    }
+}
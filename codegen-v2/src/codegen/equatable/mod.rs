@@ -0,0 +1,106 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::kotlin::KotlinType;
+use super::{rust, TargetLanguage};
+use crate::manifest::{StructInfo, TypeVariant};
+use crate::Result;
+use handlebars::Handlebars;
+
+const SWIFT_TEMPLATE: &str = include_str!("templates/swift.hbs");
+const KOTLIN_TEMPLATE: &str = include_str!("templates/kotlin.hbs");
+const RUST_TEMPLATE: &str = include_str!("templates/rust.hbs");
+
+/// Represents a struct field compared by the helpers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquatableField {
+    name: String,
+    // The Kotlin type of the field, respectively the Rust statement hashing
+    // it, e.g. `std::hash::Hash::hash(&self.x, state)`.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    field_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hash: Option<String>,
+}
+
+/// Generates field-wise equality and hashing for a value struct, to be added
+/// to the bindings of the target language: a Swift `Equatable` and `Hashable`
+/// extension, a Kotlin `data class` or Rust `PartialEq` and `Hash` impls.
+/// Returns `None` for opaque structs, which are compared by identity, and
+/// for other targets.
+pub fn generate_equatable(s: &StructInfo, target: TargetLanguage) -> Result<Option<String>> {
+    if s.fields.is_empty() {
+        return Ok(None);
+    }
+
+    let (template, name) = match target {
+        TargetLanguage::Swift => (SWIFT_TEMPLATE, stripped_name(&s.name)),
+        TargetLanguage::Kotlin => (
+            KOTLIN_TEMPLATE,
+            KotlinType::from(TypeVariant::Struct(s.name.clone())).to_string(),
+        ),
+        // The Rust bindings keep the C names.
+        TargetLanguage::Rust => (RUST_TEMPLATE, s.name.clone()),
+        _ => return Ok(None),
+    };
+
+    let fields = s
+        .fields
+        .iter()
+        .map(|field| {
+            let name = field.name.clone();
+            match target {
+                TargetLanguage::Kotlin => EquatableField {
+                    field_type: Some(KotlinType::from(field.ty.variant.clone()).to_string()),
+                    hash: None,
+                    name,
+                },
+                // Floats are not `Hash`, hence hashed by their bits, which is
+                // consistent with `==` except for zeros and NaNs. The fields
+                // are escaped like in the Rust bindings, e.g. `r#type`.
+                TargetLanguage::Rust => {
+                    let name = rust::param_name(&name);
+                    EquatableField {
+                        hash: Some(match field.ty.variant {
+                            TypeVariant::Float | TypeVariant::Double if !field.ty.is_pointer() => {
+                                format!("std::hash::Hash::hash(&self.{name}.to_bits(), state)")
+                            }
+                            _ => format!("std::hash::Hash::hash(&self.{name}, state)"),
+                        }),
+                        field_type: None,
+                        name,
+                    }
+                }
+                _ => EquatableField {
+                    field_type: None,
+                    hash: None,
+                    name,
+                },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut engine = Handlebars::new();
+    // Unmatched variables should result in an error.
+    engine.set_strict_mode(true);
+    engine.register_escape_fn(handlebars::no_escape);
+
+    engine.register_template_string("equatable", template)?;
+    let out = engine.render(
+        "equatable",
+        &serde_json::json!({
+            "name": name,
+            "fields": fields,
+        }),
+    )?;
+
+    Ok(Some(out))
+}
+
+// E.g. `TWPoint` -> `Point`.
+fn stripped_name(name: &str) -> String {
+    name.strip_prefix("TW").unwrap_or(name).to_string()
+}
@@ -0,0 +1,374 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::{c_functions, CFunction};
+use crate::manifest::{EnumInfo, FileInfo, ParamInfo, StructInfo, TypeInfo, TypeVariant};
+use crate::Result;
+use handlebars::Handlebars;
+use heck::ToUpperCamelCase;
+use std::fmt::Display;
+
+const FILE_TEMPLATE: &str = include_str!("templates/file.hbs");
+const HELPERS_TEMPLATE: &str = include_str!("templates/helpers.hbs");
+
+/// Represents a Go struct wrapping the pointer of an opaque C struct.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoStruct {
+    name: String,
+    c_name: String,
+}
+
+/// Represents a typed Go enum, declared as a list of constants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoEnum {
+    name: String,
+    value_type: GoType,
+    variants: Vec<GoEnumVariant>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoEnumVariant {
+    name: String,
+    value: String,
+}
+
+/// Represents a Go function calling the C function via cgo.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoFunction {
+    name: String,
+    c_name: String,
+    params: Vec<GoParam>,
+    // Statements to prepare the arguments, such as creating a `TWString`.
+    setup: Vec<String>,
+    // `None` if the function returns nothing.
+    #[serde(rename = "return")]
+    return_type: Option<GoReturn>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoParam {
+    name: String,
+    #[serde(rename = "type")]
+    param_type: GoType,
+    // The argument passed on to the C function.
+    arg: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoReturn {
+    #[serde(rename = "type")]
+    param_type: GoType,
+    // The function converting the C result, e.g. `goString`.
+    convert: String,
+    // The Go struct wrapping the result, for struct pointers.
+    object: Option<String>,
+    is_nullable: bool,
+}
+
+// Wrapper around a valid Go type (built in or custom). Meant to be used as
+// `<GoType as From<TypeVariant>>::from(...)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoType(String);
+
+impl Display for GoType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Convert the `TypeVariant` into the appropriate Go type.
+impl From<TypeVariant> for GoType {
+    fn from(value: TypeVariant) -> Self {
+        let res = match value {
+            TypeVariant::Void => String::new(),
+            TypeVariant::Bool => "bool".to_string(),
+            TypeVariant::Char | TypeVariant::Int8T => "int8".to_string(),
+            TypeVariant::ShortInt | TypeVariant::Int16T => "int16".to_string(),
+            TypeVariant::Int | TypeVariant::Int32T => "int32".to_string(),
+            TypeVariant::UnsignedInt | TypeVariant::UInt32T => "uint32".to_string(),
+            TypeVariant::LongInt | TypeVariant::Int64T => "int64".to_string(),
+            TypeVariant::UnsignedLongInt => "uint64".to_string(),
+            TypeVariant::Float => "float32".to_string(),
+            TypeVariant::Double => "float64".to_string(),
+            TypeVariant::SizeT => "uint".to_string(),
+            TypeVariant::IntPtr => "int".to_string(),
+            TypeVariant::UInt8T => "uint8".to_string(),
+            TypeVariant::UInt16T => "uint16".to_string(),
+            TypeVariant::UInt64T => "uint64".to_string(),
+            TypeVariant::String | TypeVariant::CString => "string".to_string(),
+            TypeVariant::Data => "[]byte".to_string(),
+            TypeVariant::Array { element, .. } => format!("[]{}", GoType::from(*element)),
+            TypeVariant::FunctionPointer { .. } => "unsafe.Pointer".to_string(),
+            TypeVariant::Unknown(raw) => raw,
+            TypeVariant::Struct(n) | TypeVariant::Enum(n) => {
+                // We strip the "TW" prefix for Go representations of
+                // structs/enums.
+                n.strip_prefix("TW").map(|n| n.to_string()).unwrap_or(n)
+            }
+        };
+
+        GoType(res)
+    }
+}
+
+/// Generates the Go cgo bindings for all public structs, enums and functions
+/// of the given file. Variadic functions and functions with types which
+/// cannot be converted, such as arrays or function pointers, are skipped.
+pub fn generate_go(file: &FileInfo) -> Result<String> {
+    let mut engine = Handlebars::new();
+    // Unmatched variables should result in an error.
+    engine.set_strict_mode(true);
+    // Types such as `[]byte` must not be HTML-escaped.
+    engine.register_escape_fn(handlebars::no_escape);
+
+    engine.register_template_string("file", FILE_TEMPLATE)?;
+
+    let structs = file
+        .structs
+        .iter()
+        .filter(|strct| strct.is_public)
+        .map(process_struct)
+        .collect::<Vec<_>>();
+
+    let enums = file
+        .enums
+        .iter()
+        .filter(|enm| enm.is_public)
+        .map(process_enum)
+        .collect::<Vec<_>>();
+
+    let functions = c_functions(file)
+        .iter()
+        .filter_map(process_function)
+        .collect::<Vec<_>>();
+
+    let out = engine.render(
+        "file",
+        &serde_json::json!({
+            "current_year": crate::current_year(),
+            "header": file.name,
+            "enums": enums,
+            "structs": structs,
+            "functions": functions,
+        }),
+    )?;
+
+    Ok(out)
+}
+
+/// Generates the `TWString` and `TWData` conversion helpers used by the
+/// generated files of the package.
+pub fn generate_go_helpers() -> Result<String> {
+    let mut engine = Handlebars::new();
+    engine.set_strict_mode(true);
+    engine.register_escape_fn(handlebars::no_escape);
+
+    engine.register_template_string("helpers", HELPERS_TEMPLATE)?;
+
+    let out = engine.render(
+        "helpers",
+        &serde_json::json!({
+            "current_year": crate::current_year(),
+        }),
+    )?;
+
+    Ok(out)
+}
+
+fn process_struct(strct: &StructInfo) -> GoStruct {
+    GoStruct {
+        name: GoType::from(TypeVariant::Struct(strct.name.clone())).0,
+        c_name: strct.name.clone(),
+    }
+}
+
+fn process_enum(enm: &EnumInfo) -> GoEnum {
+    let name = GoType::from(TypeVariant::Enum(enm.name.clone())).0;
+
+    let names = enm
+        .variants
+        .iter()
+        .map(|variant| format!("{name}{}", variant.name.to_upper_camel_case()))
+        .collect::<Vec<_>>();
+
+    // Align the constants the way `gofmt` does.
+    let width = names.iter().map(|n| n.len()).max().unwrap_or_default();
+
+    GoEnum {
+        value_type: GoType::from(enm.value_type.clone()),
+        variants: names
+            .into_iter()
+            .zip(&enm.variants)
+            .map(|(variant_name, variant)| GoEnumVariant {
+                name: format!("{variant_name:width$}"),
+                value: variant.value.clone(),
+            })
+            .collect(),
+        name,
+    }
+}
+
+// Returns `None` if any of the types is not supported.
+fn process_function(func: &CFunction) -> Option<GoFunction> {
+    // Variadic C functions cannot be called via cgo.
+    if func.is_variadic {
+        return None;
+    }
+
+    let mut setup = vec![];
+    let mut params = vec![];
+    for param in &func.params {
+        params.push(process_param(param, &mut setup)?);
+    }
+
+    let return_type = match &func.return_type {
+        Some(ty) => Some(process_return(ty)?),
+        None => None,
+    };
+
+    Some(GoFunction {
+        name: func
+            .name
+            .strip_prefix("TW")
+            .unwrap_or(&func.name)
+            .to_string(),
+        c_name: func.name.clone(),
+        params,
+        setup,
+        return_type,
+    })
+}
+
+fn process_param(param: &ParamInfo, setup: &mut Vec<String>) -> Option<GoParam> {
+    let name = param_name(&param.name);
+    let param_type = GoType::from(param.ty.variant.clone());
+
+    let arg = match (&param.ty.variant, param.ty.pointer_depth) {
+        (TypeVariant::String, 1) => {
+            setup.push(format!("{name}TW := newTWString({name})"));
+            setup.push(format!("defer C.TWStringDelete({name}TW)"));
+            format!("{name}TW")
+        }
+        (TypeVariant::Data, 1) => {
+            setup.push(format!("{name}TW := newTWData({name})"));
+            setup.push(format!("defer C.TWDataDelete({name}TW)"));
+            format!("{name}TW")
+        }
+        (TypeVariant::Struct(c_name), 1) if param.ty.is_nullable => {
+            setup.push(format!("var {name}Ptr *C.struct_{c_name}"));
+            setup.push(format!(
+                "if {name} != nil {{\n\t\t{name}Ptr = {name}.ptr\n\t}}"
+            ));
+            format!("{name}Ptr")
+        }
+        (TypeVariant::Struct(_), 1) => format!("{name}.ptr"),
+        (TypeVariant::Enum(c_name), 0) => format!("C.enum_{c_name}({name})"),
+        (variant, 0) => format!("C.{}({name})", c_type(variant)?),
+        _ => return None,
+    };
+
+    Some(GoParam {
+        name,
+        param_type: GoType(match param.ty.variant {
+            TypeVariant::Struct(_) => format!("*{param_type}"),
+            _ => param_type.0,
+        }),
+        arg,
+    })
+}
+
+fn process_return(ty: &TypeInfo) -> Option<GoReturn> {
+    let go_type = GoType::from(ty.variant.clone());
+
+    let (param_type, convert, object) = match (&ty.variant, ty.pointer_depth) {
+        (TypeVariant::String, 1) => (go_type, "goString".to_string(), None),
+        (TypeVariant::Data, 1) => (go_type, "goBytes".to_string(), None),
+        (TypeVariant::Struct(_), 1) => (
+            GoType(format!("*{go_type}")),
+            String::new(),
+            Some(go_type.0),
+        ),
+        (TypeVariant::Enum(_), 0) => (go_type.clone(), go_type.0, None),
+        (variant, 0) => {
+            // Check that the type has a cgo equivalent.
+            c_type(variant)?;
+            (go_type.clone(), go_type.0, None)
+        }
+        _ => return None,
+    };
+
+    Some(GoReturn {
+        param_type,
+        convert,
+        object,
+        is_nullable: ty.is_nullable,
+    })
+}
+
+// Returns the cgo name of primitive C types, e.g. `uint32_t` for `C.uint32_t`.
+fn c_type(variant: &TypeVariant) -> Option<&'static str> {
+    let res = match variant {
+        TypeVariant::Bool => "bool",
+        TypeVariant::Char => "char",
+        TypeVariant::ShortInt => "short",
+        TypeVariant::Int => "int",
+        TypeVariant::UnsignedInt => "uint",
+        TypeVariant::LongInt => "long",
+        TypeVariant::UnsignedLongInt => "ulong",
+        TypeVariant::Float => "float",
+        TypeVariant::Double => "double",
+        TypeVariant::SizeT => "size_t",
+        TypeVariant::IntPtr => "intptr_t",
+        TypeVariant::Int8T => "int8_t",
+        TypeVariant::Int16T => "int16_t",
+        TypeVariant::Int32T => "int32_t",
+        TypeVariant::Int64T => "int64_t",
+        TypeVariant::UInt8T => "uint8_t",
+        TypeVariant::UInt16T => "uint16_t",
+        TypeVariant::UInt32T => "uint32_t",
+        TypeVariant::UInt64T => "uint64_t",
+        _ => return None,
+    };
+
+    Some(res)
+}
+
+// Escapes parameter names which are Go keywords, such as `type` or `func`.
+fn param_name(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "break",
+        "case",
+        "chan",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "else",
+        "fallthrough",
+        "for",
+        "func",
+        "go",
+        "goto",
+        "if",
+        "import",
+        "interface",
+        "map",
+        "package",
+        "range",
+        "return",
+        "select",
+        "struct",
+        "switch",
+        "type",
+        "var",
+    ];
+
+    if KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
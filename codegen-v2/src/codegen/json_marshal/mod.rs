@@ -0,0 +1,136 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::{transform_name, NameStyle, TargetLanguage};
+use crate::manifest::{classify_structs, FileInfo, Manifest, StructInfo, StructKind, TypeVariant};
+use crate::{Error, Result};
+use handlebars::Handlebars;
+
+const PYTHON_TEMPLATE: &str = include_str!("templates/python.hbs");
+
+/// Represents the marshalling functions of a value struct.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarshalStruct {
+    name: String,
+    // The prefix of the function names, e.g. `point` for `point_to_json`.
+    prefix: String,
+    fields: Vec<MarshalField>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarshalField {
+    name: String,
+    // The expressions converting the field from the value into a JSON value,
+    // and vice versa.
+    to: String,
+    from: String,
+}
+
+/// Generates functions converting the value structs of the given file from
+/// and to JSON, for the bindings of the target language. Opaque structs are
+/// skipped, as are structs with fields which cannot be represented, such as
+/// pointers. Only Python is supported for now.
+pub fn generate_marshal(file: &FileInfo, target: TargetLanguage) -> Result<String> {
+    if target != TargetLanguage::Python {
+        return Err(Error::UnsupportedTarget(format!(
+            "JSON marshalling is not supported for {target:?}"
+        )));
+    }
+
+    let mut engine = Handlebars::new();
+    // Unmatched variables should result in an error.
+    engine.set_strict_mode(true);
+    // Escaping is not required for Python sources.
+    engine.register_escape_fn(handlebars::no_escape);
+
+    engine.register_template_string("python", PYTHON_TEMPLATE)?;
+
+    let kinds = classify_structs(&Manifest {
+        files: vec![file.clone()],
+        ..Default::default()
+    });
+
+    let structs = file
+        .structs
+        .iter()
+        .filter(|strct| kinds.get(&strct.name) == Some(&StructKind::Value))
+        .filter_map(|strct| process_struct(strct, file))
+        .collect::<Vec<_>>();
+
+    let out = engine.render(
+        "python",
+        &serde_json::json!({
+            "current_year": crate::current_year(),
+            "module": file.name,
+            "structs": structs,
+        }),
+    )?;
+
+    Ok(out)
+}
+
+// Returns `None` if any of the fields is not supported.
+fn process_struct(strct: &StructInfo, file: &FileInfo) -> Option<MarshalStruct> {
+    // Structs without fields are not declared by the Python bindings.
+    if strct.fields.is_empty() {
+        return None;
+    }
+
+    let mut fields = vec![];
+    for field in &strct.fields {
+        if field.ty.is_pointer() {
+            return None;
+        }
+
+        let name = field.name.clone();
+        let (to, from) = match &field.ty.variant {
+            // Nested value structs must be declared by the same file.
+            TypeVariant::Struct(nested) => {
+                let nested = file.structs.iter().find(|s| &s.name == nested)?;
+                process_struct(nested, file)?;
+
+                let prefix = prefix(&nested.name);
+                (
+                    format!("{prefix}_to_dict(value.{name})"),
+                    format!("{prefix}_from_dict(data[\"{name}\"])"),
+                )
+            }
+            TypeVariant::Enum(_) => (format!("int(value.{name})"), format!("data[\"{name}\"]")),
+            TypeVariant::Bool
+            | TypeVariant::ShortInt
+            | TypeVariant::Int
+            | TypeVariant::UnsignedInt
+            | TypeVariant::LongInt
+            | TypeVariant::UnsignedLongInt
+            | TypeVariant::Float
+            | TypeVariant::Double
+            | TypeVariant::SizeT
+            | TypeVariant::IntPtr
+            | TypeVariant::Int8T
+            | TypeVariant::Int16T
+            | TypeVariant::Int32T
+            | TypeVariant::Int64T
+            | TypeVariant::UInt8T
+            | TypeVariant::UInt16T
+            | TypeVariant::UInt32T
+            | TypeVariant::UInt64T => (format!("value.{name}"), format!("data[\"{name}\"]")),
+            _ => return None,
+        };
+
+        fields.push(MarshalField { name, to, from });
+    }
+
+    Some(MarshalStruct {
+        name: strct.name.clone(),
+        prefix: prefix(&strct.name),
+        fields,
+    })
+}
+
+// E.g. `TWPoint` -> `point`.
+fn prefix(name: &str) -> String {
+    transform_name(name, Some("TW"), NameStyle::SnakeCase)
+}
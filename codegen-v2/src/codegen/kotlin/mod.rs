@@ -0,0 +1,333 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::{explicit_params, take_associated, take_functions, transform_name, NameStyle};
+use crate::manifest::{
+    EnumInfo, FileInfo, FunctionInfo, InitInfo, ParamInfo, PropertyInfo, StructInfo, TypeInfo,
+    TypeVariant,
+};
+use crate::Result;
+use handlebars::Handlebars;
+use heck::ToUpperCamelCase;
+use std::fmt::Display;
+
+const FILE_TEMPLATE: &str = include_str!("templates/file.hbs");
+const CLASS_TEMPLATE: &str = include_str!("templates/class.hbs");
+const OBJECT_TEMPLATE: &str = include_str!("templates/object.hbs");
+const ENUM_TEMPLATE: &str = include_str!("templates/enum.hbs");
+
+/// Represents a Kotlin class (or object, for non-class structs) whose methods
+/// are `external` JNI stubs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KotlinClass {
+    name: String,
+    is_class: bool,
+    constructors: Vec<KotlinFunction>,
+    methods: Vec<KotlinFunction>,
+    static_methods: Vec<KotlinFunction>,
+    properties: Vec<KotlinProperty>,
+}
+
+/// Represents a Kotlin enum class.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KotlinEnum {
+    name: String,
+    value_type: KotlinType,
+    // Literal suffix of the variant values, e.g. `u` for unsigned types.
+    value_suffix: String,
+    has_string: bool,
+    variants: Vec<KotlinEnumVariant>,
+    methods: Vec<KotlinFunction>,
+    properties: Vec<KotlinProperty>,
+}
+
+/// Represents a Kotlin enum variant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KotlinEnumVariant {
+    name: String,
+    value: String,
+    as_string: Option<String>,
+}
+
+/// Represents a Kotlin function, method or constructor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KotlinFunction {
+    name: String,
+    is_nullable: bool,
+    params: Vec<KotlinParam>,
+    // `None` if the function returns `Unit`.
+    #[serde(rename = "return")]
+    return_type: Option<KotlinReturn>,
+}
+
+/// Represents a Kotlin property with an `external` getter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KotlinProperty {
+    name: String,
+    #[serde(rename = "return")]
+    return_type: KotlinReturn,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KotlinParam {
+    name: String,
+    #[serde(rename = "type")]
+    param_type: KotlinType,
+    is_nullable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KotlinReturn {
+    #[serde(rename = "type")]
+    param_type: KotlinType,
+    is_nullable: bool,
+}
+
+// Wrapper around a valid Kotlin type (built in or custom). Meant to be used as
+// `<KotlinType as From<TypeVariant>>::from(...)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KotlinType(String);
+
+impl Display for KotlinType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Convert the `TypeVariant` into the appropriate Kotlin type.
+impl From<TypeVariant> for KotlinType {
+    fn from(value: TypeVariant) -> Self {
+        let res = match value {
+            TypeVariant::Void => "Unit".to_string(),
+            TypeVariant::Bool => "Boolean".to_string(),
+            TypeVariant::Char => "Char".to_string(),
+            TypeVariant::ShortInt => "Short".to_string(),
+            TypeVariant::Int => "Int".to_string(),
+            TypeVariant::UnsignedInt => "UInt".to_string(),
+            TypeVariant::LongInt => "Long".to_string(),
+            TypeVariant::UnsignedLongInt => "ULong".to_string(),
+            TypeVariant::Float => "Float".to_string(),
+            TypeVariant::Double => "Double".to_string(),
+            TypeVariant::SizeT => "ULong".to_string(),
+            TypeVariant::IntPtr => "Long".to_string(),
+            TypeVariant::Int8T => "Byte".to_string(),
+            TypeVariant::Int16T => "Short".to_string(),
+            TypeVariant::Int32T => "Int".to_string(),
+            TypeVariant::Int64T => "Long".to_string(),
+            TypeVariant::UInt8T => "UByte".to_string(),
+            TypeVariant::UInt16T => "UShort".to_string(),
+            TypeVariant::UInt32T => "UInt".to_string(),
+            TypeVariant::UInt64T => "ULong".to_string(),
+            TypeVariant::String | TypeVariant::CString => "String".to_string(),
+            TypeVariant::Data => "ByteArray".to_string(),
+            TypeVariant::Array { element, .. } => {
+                format!("Array<{}>", KotlinType::from(*element))
+            }
+            // E.g. `(Int) -> Unit`
+            TypeVariant::FunctionPointer { ret, params } => {
+                let params = params
+                    .into_iter()
+                    .map(|ty| KotlinType::from(ty.variant).0)
+                    .collect::<Vec<_>>();
+
+                format!(
+                    "({}) -> {}",
+                    params.join(", "),
+                    KotlinType::from(ret.variant)
+                )
+            }
+            TypeVariant::Unknown(raw) => raw,
+            TypeVariant::Struct(n) | TypeVariant::Enum(n) => {
+                // We strip the "TW" prefix for Kotlin representations of
+                // structs/enums.
+                n.strip_prefix("TW").map(|n| n.to_string()).unwrap_or(n)
+            }
+        };
+
+        KotlinType(res)
+    }
+}
+
+impl From<&ParamInfo> for KotlinParam {
+    fn from(param: &ParamInfo) -> Self {
+        KotlinParam {
+            name: param.name.clone(),
+            param_type: KotlinType::from(param.ty.variant.clone()),
+            is_nullable: param.ty.is_nullable,
+        }
+    }
+}
+
+impl From<&TypeInfo> for KotlinReturn {
+    fn from(ty: &TypeInfo) -> Self {
+        KotlinReturn {
+            param_type: KotlinType::from(ty.variant.clone()),
+            is_nullable: ty.is_nullable,
+        }
+    }
+}
+
+/// Generates the Kotlin JNI bindings for all structs and enums of the given
+/// file, rendered into a single Kotlin source file.
+pub fn generate_kotlin(file: &FileInfo) -> Result<String> {
+    let mut engine = Handlebars::new();
+    // Unmatched variables should result in an error.
+    engine.set_strict_mode(true);
+    // Kotlin generics must not be HTML-escaped.
+    engine.register_escape_fn(handlebars::no_escape);
+
+    engine.register_template_string("file", FILE_TEMPLATE)?;
+    engine.register_partial("class", CLASS_TEMPLATE)?;
+    engine.register_partial("object", OBJECT_TEMPLATE)?;
+    engine.register_partial("enum", ENUM_TEMPLATE)?;
+
+    let mut inits = file.inits.clone();
+    let mut functions = file.functions.clone();
+    let mut properties = file.properties.clone();
+
+    let mut classes = vec![];
+    for strct in &file.structs {
+        let class = process_struct(strct, &mut inits, &mut functions, &mut properties);
+
+        // Avoid rendering empty classes.
+        if class.constructors.is_empty()
+            && class.methods.is_empty()
+            && class.static_methods.is_empty()
+            && class.properties.is_empty()
+        {
+            continue;
+        }
+
+        classes.push(class);
+    }
+
+    let enums = file
+        .enums
+        .iter()
+        .map(|enm| process_enum(enm, &mut functions, &mut properties))
+        .collect::<Vec<_>>();
+
+    let out = engine.render(
+        "file",
+        &serde_json::json!({
+            "current_year": crate::current_year(),
+            "enums": enums,
+            "classes": classes,
+        }),
+    )?;
+
+    Ok(out)
+}
+
+fn process_struct(
+    strct: &StructInfo,
+    inits: &mut Vec<InitInfo>,
+    functions: &mut Vec<FunctionInfo>,
+    properties: &mut Vec<PropertyInfo>,
+) -> KotlinClass {
+    let constructors = take_associated(inits, &strct.name, |init| &init.name)
+        .into_iter()
+        .map(|init| KotlinFunction {
+            name: transform_name(&init.name, Some(&strct.name), NameStyle::LowerCamelCase),
+            is_nullable: init.is_nullable,
+            params: init.params.iter().map(KotlinParam::from).collect(),
+            return_type: None,
+        })
+        .collect();
+
+    // Non-class structs are rendered as Kotlin objects, which only have
+    // static methods.
+    let (mut methods, mut static_methods) = (vec![], vec![]);
+    for func in take_functions(functions, &strct.name) {
+        let is_static = func.is_static || !strct.is_class;
+        let func = process_function(func, &strct.name);
+
+        if is_static {
+            static_methods.push(func);
+        } else {
+            methods.push(func);
+        }
+    }
+
+    KotlinClass {
+        name: KotlinType::from(TypeVariant::Struct(strct.name.clone())).0,
+        is_class: strct.is_class,
+        constructors,
+        methods,
+        static_methods,
+        properties: process_properties(properties, &strct.name),
+    }
+}
+
+fn process_enum(
+    enm: &EnumInfo,
+    functions: &mut Vec<FunctionInfo>,
+    properties: &mut Vec<PropertyInfo>,
+) -> KotlinEnum {
+    let value_suffix = match enm.value_type {
+        TypeVariant::UnsignedInt
+        | TypeVariant::UInt8T
+        | TypeVariant::UInt16T
+        | TypeVariant::UInt32T
+        | TypeVariant::UInt64T => "u",
+        _ => "",
+    };
+
+    let variants = enm
+        .variants
+        .iter()
+        .map(|variant| KotlinEnumVariant {
+            name: variant.name.to_upper_camel_case(),
+            value: variant.value.clone(),
+            as_string: variant.as_string.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    let methods = take_functions(functions, &enm.name)
+        .into_iter()
+        .map(|func| process_function(func, &enm.name))
+        .collect();
+
+    KotlinEnum {
+        name: KotlinType::from(TypeVariant::Enum(enm.name.clone())).0,
+        value_type: KotlinType::from(enm.value_type.clone()),
+        value_suffix: value_suffix.to_string(),
+        has_string: !variants.is_empty() && variants.iter().all(|v| v.as_string.is_some()),
+        variants,
+        methods,
+        properties: process_properties(properties, &enm.name),
+    }
+}
+
+fn process_function(func: FunctionInfo, object_name: &str) -> KotlinFunction {
+    // Skip the 'self' parameter, which is passed on by the JNI layer.
+    let params = explicit_params(&func, object_name)
+        .iter()
+        .map(KotlinParam::from)
+        .collect();
+
+    let return_type = (!func.return_type.is_unit()).then(|| KotlinReturn::from(&func.return_type));
+
+    KotlinFunction {
+        name: transform_name(&func.name, Some(object_name), NameStyle::LowerCamelCase),
+        is_nullable: func.return_type.is_nullable,
+        params,
+        return_type,
+    }
+}
+
+fn process_properties(
+    properties: &mut Vec<PropertyInfo>,
+    object_name: &str,
+) -> Vec<KotlinProperty> {
+    take_associated(properties, object_name, |p| &p.name)
+        .into_iter()
+        .map(|prop| KotlinProperty {
+            name: transform_name(&prop.name, Some(object_name), NameStyle::LowerCamelCase),
+            return_type: KotlinReturn::from(&prop.return_type),
+        })
+        .collect()
+}
@@ -4,4 +4,412 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
+use crate::manifest::{
+    FileInfo, FunctionInfo, Manifest, ParamInfo, StructInfo, TypeInfo, TypeVariant,
+};
+use crate::{Error, Result};
+use handlebars::Handlebars;
+use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use serde_json::json;
+use std::path::PathBuf;
+use swift::RenderIntput;
+
+pub mod csharp;
+pub mod equatable;
+pub mod go;
+pub mod json_marshal;
+pub mod kotlin;
+pub mod objc;
+pub mod protobuf;
+pub mod python;
+pub mod rust;
 pub mod swift;
+pub mod typescript;
+
+/// The languages bindings can be generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetLanguage {
+    CSharp,
+    Go,
+    Kotlin,
+    ObjC,
+    Protobuf,
+    Python,
+    Rust,
+    Swift,
+    TypeScript,
+}
+
+impl TargetLanguage {
+    /// All targets, in alphabetical order.
+    pub const ALL: [TargetLanguage; 9] = [
+        TargetLanguage::CSharp,
+        TargetLanguage::Go,
+        TargetLanguage::Kotlin,
+        TargetLanguage::ObjC,
+        TargetLanguage::Protobuf,
+        TargetLanguage::Python,
+        TargetLanguage::Rust,
+        TargetLanguage::Swift,
+        TargetLanguage::TypeScript,
+    ];
+
+    /// The file extension of the output rendered by `generate`. Swift and
+    /// Objective-C render multiple files, hence have none.
+    pub fn extension(&self) -> Option<&'static str> {
+        let extension = match self {
+            TargetLanguage::CSharp => "cs",
+            TargetLanguage::Go => "go",
+            TargetLanguage::Kotlin => "kt",
+            TargetLanguage::Protobuf => "proto",
+            TargetLanguage::Python => "py",
+            TargetLanguage::Rust => "rs",
+            TargetLanguage::TypeScript => "d.ts",
+            TargetLanguage::ObjC | TargetLanguage::Swift => return None,
+        };
+
+        Some(extension)
+    }
+
+    /// The name of the file written by `generate_index`, if the target needs
+    /// one.
+    pub fn index_file(&self) -> Option<&'static str> {
+        let index_file = match self {
+            TargetLanguage::ObjC => "WalletCore.h",
+            TargetLanguage::Python => "__init__.py",
+            TargetLanguage::Rust => "mod.rs",
+            TargetLanguage::TypeScript => "index.d.ts",
+            TargetLanguage::CSharp
+            | TargetLanguage::Go
+            | TargetLanguage::Kotlin
+            | TargetLanguage::Protobuf
+            | TargetLanguage::Swift => return None,
+        };
+
+        Some(index_file)
+    }
+}
+
+/// Generates the bindings of the given file for the target language, as a
+/// single source file. Swift and Objective-C render multiple files per
+/// manifest file, hence have to be generated via `generate_file`.
+pub fn generate(file: &FileInfo, target: TargetLanguage) -> Result<String> {
+    match target {
+        TargetLanguage::CSharp => csharp::generate_csharp(file),
+        TargetLanguage::Go => go::generate_go(file),
+        TargetLanguage::Kotlin => kotlin::generate_kotlin(file),
+        TargetLanguage::Protobuf => protobuf::generate_proto(file),
+        TargetLanguage::Python => python::generate_python(file),
+        TargetLanguage::Rust => rust::generate_rust(file),
+        // Without the other files, no types can be imported.
+        TargetLanguage::TypeScript => {
+            typescript::generate_dts(file, &Manifest::from_files(vec![file.clone()]))
+        }
+        TargetLanguage::ObjC => Err(unsupported(file, "objc")),
+        TargetLanguage::Swift => Err(unsupported(file, "swift")),
+    }
+}
+
+/// Generates the bindings of the given file for the target language,
+/// returning each output alongside its path relative to the output directory.
+/// Unlike `generate`, this supports Swift and Objective-C, which render
+/// multiple files per manifest file. The `manifest` resolves the types which
+/// are declared by other files.
+pub fn generate_file(
+    file: &FileInfo,
+    manifest: &Manifest,
+    target: TargetLanguage,
+) -> Result<Vec<(PathBuf, String)>> {
+    let outputs = match (target, target.extension()) {
+        (TargetLanguage::Swift, _) => swift::render_to_files(RenderIntput::new(file.clone()))?,
+        // Both files are named after the Objective-C class.
+        (TargetLanguage::ObjC, _) => {
+            let (header, implementation) = objc::generate_objc(file)?;
+            let name = file.name.strip_prefix("TW").unwrap_or(&file.name);
+            vec![
+                (PathBuf::from(format!("{name}.h")), header),
+                (PathBuf::from(format!("{name}.m")), implementation),
+            ]
+        }
+        (target, Some(extension)) => {
+            let path = PathBuf::from(format!("{}.{extension}", file.name));
+            let rendered = match target {
+                TargetLanguage::TypeScript => typescript::generate_dts(file, manifest)?,
+                target => generate(file, target)?,
+            };
+            vec![(path, rendered)]
+        }
+        (target, None) => return Err(unsupported(file, &format!("{target:?}"))),
+    };
+
+    Ok(outputs)
+}
+
+/// Generates the bindings of all given files for the target language, see
+/// `generate_file`, followed by the index of the target, if any.
+pub fn generate_files(
+    files: &[FileInfo],
+    target: TargetLanguage,
+) -> Result<Vec<(PathBuf, String)>> {
+    let manifest = Manifest::from_files(files.to_vec());
+
+    let mut outputs = vec![];
+    for file in files {
+        outputs.extend(generate_file(file, &manifest, target)?);
+    }
+
+    if let Some(index_file) = target.index_file() {
+        outputs.push((PathBuf::from(index_file), generate_index(files, target)?));
+    }
+
+    Ok(outputs)
+}
+
+/// Generates the aggregate file of the target language, which re-exports the
+/// bindings of all given files: an `index.d.ts` for TypeScript, an
+/// `__init__.py` for Python, a `mod.rs` for Rust and an umbrella header for
+/// Objective-C. The other targets declare all bindings in a single package,
+/// hence need no such file.
+pub fn generate_index(files: &[FileInfo], target: TargetLanguage) -> Result<String> {
+    let names = files.iter().map(|file| json!(file.name));
+    let (template, modules) = match target {
+        TargetLanguage::TypeScript => (
+            include_str!("templates/index.ts.hbs"),
+            names.collect::<Vec<_>>(),
+        ),
+        TargetLanguage::Python => (include_str!("templates/index.py.hbs"), names.collect()),
+        // The modules are named in snake case, since they would otherwise
+        // clash with the types they export.
+        TargetLanguage::Rust => (
+            include_str!("templates/index.rs.hbs"),
+            files
+                .iter()
+                .map(|file| json!({ "file": file.name, "name": file.name.to_snake_case() }))
+                .collect(),
+        ),
+        // The headers are named after the Objective-C classes.
+        TargetLanguage::ObjC => (
+            include_str!("templates/index.h.hbs"),
+            files
+                .iter()
+                .map(|file| json!(file.name.strip_prefix("TW").unwrap_or(&file.name)))
+                .collect(),
+        ),
+        target => {
+            return Err(Error::UnsupportedTarget(format!(
+                "{target:?} bindings are declared in a single package, which needs no index"
+            )))
+        }
+    };
+
+    let mut engine = Handlebars::new();
+    // Unmatched variables should result in an error.
+    engine.set_strict_mode(true);
+    engine.register_escape_fn(handlebars::no_escape);
+
+    engine.register_template_string("index", template)?;
+
+    let out = engine.render(
+        "index",
+        &json!({
+            "current_year": crate::current_year(),
+            "modules": modules,
+        }),
+    )?;
+
+    Ok(out)
+}
+
+fn unsupported(file: &FileInfo, module: &str) -> Error {
+    Error::UnsupportedTarget(format!(
+        "`{module}` renders multiple files for {}, hence cannot be generated as a single file",
+        file.name
+    ))
+}
+
+// Fails if the struct has bitfields, which the target cannot declare. Since
+// their layout is implementation defined, they are rejected rather than
+// widened to the full field type, which would break the struct layout.
+pub(crate) fn reject_bitfields(strct: &StructInfo, target: TargetLanguage) -> Result<()> {
+    match strct.fields.iter().find(|field| field.bit_width.is_some()) {
+        Some(field) => Err(Error::UnsupportedTarget(format!(
+            "{target:?} bindings cannot declare the bitfield `{}` of {}",
+            field.name, strct.name
+        ))),
+        None => Ok(()),
+    }
+}
+
+// Convenience function: removes the items associated with the given object,
+// based on common name prefix, and returns them.
+pub(crate) fn take_associated<T, F>(items: &mut Vec<T>, object_name: &str, name: F) -> Vec<T>
+where
+    F: Fn(&T) -> &String,
+{
+    let (associated, skipped) = std::mem::take(items)
+        .into_iter()
+        .partition(|item| name(item).starts_with(object_name));
+
+    *items = skipped;
+    associated
+}
+
+// Convenience function: removes the functions associated with the given object
+// and returns them.
+pub(crate) fn take_functions(
+    functions: &mut Vec<FunctionInfo>,
+    object_name: &str,
+) -> Vec<FunctionInfo> {
+    let (associated, skipped): (Vec<_>, _) = std::mem::take(functions)
+        .into_iter()
+        .partition(|func| func.is_associated_with(object_name));
+
+    *functions = skipped;
+    // Variadic C functions cannot be called by the bindings.
+    associated
+        .into_iter()
+        .filter(|func| !func.is_variadic)
+        .collect()
+}
+
+/// The casing of generated names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameStyle {
+    /// E.g. `isValid`, for Swift, Kotlin and TypeScript members.
+    LowerCamelCase,
+    /// E.g. `IsValid`, for types and enum variants.
+    UpperCamelCase,
+    /// E.g. `is_valid`, for Rust and Python members.
+    SnakeCase,
+    /// E.g. `IS_VALID`, for constants.
+    ShoutySnakeCase,
+}
+
+/// Converts a C name into the given style, removing the name of the owning
+/// type first, e.g. `TWMnemonicIsValid` of `TWMnemonic` -> `isValid`. Names
+/// without the prefix are converted as they are.
+pub fn transform_name(raw: &str, owning_type: Option<&str>, style: NameStyle) -> String {
+    let name = owning_type
+        .and_then(|prefix| raw.strip_prefix(prefix))
+        .unwrap_or(raw);
+
+    match style {
+        NameStyle::LowerCamelCase => name.to_lower_camel_case(),
+        NameStyle::UpperCamelCase => name.to_upper_camel_case(),
+        NameStyle::SnakeCase => name.to_snake_case(),
+        NameStyle::ShoutySnakeCase => name.to_shouty_snake_case(),
+    }
+}
+
+// Convenience function: whether the parameter is the 'self' instance of the
+// object, which is passed on implicitly by the bindings.
+pub(crate) fn is_self_param(param: &ParamInfo, object_name: &str) -> bool {
+    match &param.ty.variant {
+        TypeVariant::Struct(name) | TypeVariant::Enum(name) => name == object_name,
+        _ => false,
+    }
+}
+
+// Convenience function: the parameters as exposed by the bindings, i.e.
+// without the leading 'self' parameter of instance methods. Static functions
+// keep all parameters, e.g. both of `TWFooEqual(lhs, rhs)`.
+pub(crate) fn explicit_params<'a>(func: &'a FunctionInfo, object_name: &str) -> &'a [ParamInfo] {
+    match func.params.split_first() {
+        Some((first, rest)) if !func.is_static && is_self_param(first, object_name) => rest,
+        _ => &func.params,
+    }
+}
+
+/// A plain C function as exported by the library, for generators which bind
+/// to the C interface directly.
+pub(crate) struct CFunction {
+    pub name: String,
+    pub params: Vec<ParamInfo>,
+    // `None` if the function returns `void`.
+    pub return_type: Option<TypeInfo>,
+    pub is_variadic: bool,
+}
+
+// Convenience function: lists the C functions of all public inits, deinits,
+// functions and properties of the file, in that order.
+pub(crate) fn c_functions(file: &FileInfo) -> Vec<CFunction> {
+    // Enums are passed by value, structs by pointer.
+    let self_param = |object: &str| {
+        let is_enum = file.enums.iter().any(|enm| enm.name == object);
+        ParamInfo {
+            name: if is_enum { "value" } else { "obj" }.to_string(),
+            ty: object_type(object, is_enum, false),
+        }
+    };
+
+    let mut functions = vec![];
+
+    for init in file.inits.iter().filter(|init| init.is_public) {
+        let object = file.associated_type(&init.name).unwrap_or(&init.name);
+
+        functions.push(CFunction {
+            name: init.name.clone(),
+            params: init.params.clone(),
+            return_type: Some(object_type(object, false, init.is_nullable)),
+            is_variadic: false,
+        });
+    }
+
+    for deinit in &file.deinits {
+        let Some(object) = file.associated_type(&deinit.name) else {
+            continue;
+        };
+
+        functions.push(CFunction {
+            name: deinit.name.clone(),
+            params: vec![self_param(object)],
+            return_type: None,
+            is_variadic: false,
+        });
+    }
+
+    for func in file.functions.iter().filter(|func| func.is_public) {
+        let return_type = (!func.return_type.is_unit()).then(|| func.return_type.clone());
+
+        functions.push(CFunction {
+            name: func.name.clone(),
+            params: func.params.clone(),
+            return_type,
+            is_variadic: func.is_variadic,
+        });
+    }
+
+    // Properties are getters which take the object (or enum value) as their
+    // only parameter.
+    for prop in file.properties.iter().filter(|prop| prop.is_public) {
+        let Some(object) = file.associated_type(&prop.name) else {
+            continue;
+        };
+
+        functions.push(CFunction {
+            name: prop.name.clone(),
+            params: vec![self_param(object)],
+            return_type: Some(prop.return_type.clone()),
+            is_variadic: false,
+        });
+    }
+
+    functions
+}
+
+// E.g. `struct TWFoo*` or `enum TWFoo`.
+fn object_type(object: &str, is_enum: bool, is_nullable: bool) -> TypeInfo {
+    let (variant, pointer_depth) = if is_enum {
+        (TypeVariant::Enum(object.to_string()), 0)
+    } else {
+        (TypeVariant::Struct(object.to_string()), 1)
+    };
+
+    TypeInfo {
+        variant,
+        is_constant: false,
+        is_nullable,
+        pointer_depth,
+        is_pointer_const: false,
+    }
+}
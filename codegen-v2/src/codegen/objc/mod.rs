@@ -0,0 +1,604 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::{
+    is_self_param, object_type, take_associated, take_functions, transform_name, NameStyle,
+};
+use crate::manifest::{
+    DeinitInfo, EnumInfo, FileInfo, FunctionInfo, InitInfo, ParamInfo, PropertyInfo, StructInfo,
+    TypeInfo, TypeVariant,
+};
+use crate::Result;
+use handlebars::Handlebars;
+use heck::ToUpperCamelCase;
+use std::fmt::Display;
+
+const HEADER_TEMPLATE: &str = include_str!("templates/header.hbs");
+const IMPLEMENTATION_TEMPLATE: &str = include_str!("templates/implementation.hbs");
+const METHOD_TEMPLATE: &str = include_str!("templates/method.hbs");
+
+/// Represents an `@interface` wrapping a C struct. Class structs own the
+/// `rawValue` pointer, other structs only have class methods.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjcClass {
+    name: String,
+    c_name: String,
+    is_class: bool,
+    // The C function releasing the `rawValue`, called by `dealloc`.
+    deinit: Option<String>,
+    initializers: Vec<ObjcMethod>,
+    properties: Vec<ObjcProperty>,
+    methods: Vec<ObjcMethod>,
+}
+
+/// Represents an `NS_ENUM`. Since enums cannot have methods, the associated
+/// functions are plain C functions taking the enum value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjcEnum {
+    name: String,
+    value_type: ObjcType,
+    variants: Vec<ObjcEnumVariant>,
+    functions: Vec<ObjcMethod>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjcEnumVariant {
+    name: String,
+    value: String,
+}
+
+/// Represents a method (or C function) declared by the header, along with
+/// the statements of its implementation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjcMethod {
+    // E.g. `- (nullable NSString *)sign:(NSData *)data`
+    declaration: String,
+    body: Vec<String>,
+}
+
+/// Represents a read-only `@property`, implemented by its getter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjcProperty {
+    declaration: String,
+    getter: ObjcMethod,
+}
+
+// Wrapper around a valid Objective-C type, such as `NSString *` or `BOOL`.
+// Meant to be used as `<ObjcType as TryFrom<&TypeInfo>>::try_from(...)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjcType(String);
+
+impl Display for ObjcType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Convert the `TypeInfo` into the appropriate Objective-C type. Arrays,
+/// function pointers and pointers to primitives are not supported.
+impl TryFrom<&TypeInfo> for ObjcType {
+    type Error = ();
+
+    fn try_from(ty: &TypeInfo) -> std::result::Result<Self, Self::Error> {
+        let res = match (&ty.variant, ty.pointer_depth) {
+            (TypeVariant::String, 1) => "NSString *".to_string(),
+            (TypeVariant::Data, 1) => "NSData *".to_string(),
+            (TypeVariant::Struct(n), 1) => format!("{} *", objc_name(n)),
+            (TypeVariant::Enum(n), 0) => objc_name(n),
+            (TypeVariant::Bool, 0) => "BOOL".to_string(),
+            (variant, 0) => c_type(variant).ok_or(())?,
+            (TypeVariant::Void, 1) => "void *".to_string(),
+            _ => return Err(()),
+        };
+
+        Ok(ObjcType(res))
+    }
+}
+
+impl ObjcType {
+    // Whether the type is an object (or pointer), which can be `nil`.
+    fn is_pointer(&self) -> bool {
+        self.0.ends_with('*')
+    }
+}
+
+/// Generates the Objective-C wrappers for all public structs and enums of
+/// the given file, returning the header (`.h`) and the implementation (`.m`)
+/// file. Methods with types which cannot be converted, such as arrays or
+/// function pointers, are skipped.
+pub fn generate_objc(file: &FileInfo) -> Result<(String, String)> {
+    let mut engine = Handlebars::new();
+    // Unmatched variables should result in an error.
+    engine.set_strict_mode(true);
+    // Pointer types such as `NSString *` must not be HTML-escaped.
+    engine.register_escape_fn(handlebars::no_escape);
+
+    engine.register_template_string("header", HEADER_TEMPLATE)?;
+    engine.register_template_string("implementation", IMPLEMENTATION_TEMPLATE)?;
+    engine.register_partial("method", METHOD_TEMPLATE)?;
+
+    let mut inits = file.inits.clone();
+    let mut deinits = file.deinits.clone();
+    let mut functions = file.functions.clone();
+    let mut properties = file.properties.clone();
+
+    let mut classes = vec![];
+    for strct in file.structs.iter().filter(|strct| strct.is_public) {
+        let class = process_struct(
+            strct,
+            &mut inits,
+            &mut deinits,
+            &mut functions,
+            &mut properties,
+        );
+
+        // Avoid rendering empty classes.
+        if !class.is_class && class.methods.is_empty() {
+            continue;
+        }
+
+        classes.push(class);
+    }
+
+    let enums = file
+        .enums
+        .iter()
+        .filter(|enm| enm.is_public)
+        .map(|enm| process_enum(enm, &mut functions, &mut properties))
+        .collect::<Vec<_>>();
+
+    let context = serde_json::json!({
+        "current_year": crate::current_year(),
+        "header": file.name,
+        "name": objc_name(&file.name),
+        "enums": enums,
+        "classes": classes,
+    });
+
+    let header = engine.render("header", &context)?;
+    let implementation = engine.render("implementation", &context)?;
+
+    Ok((header, implementation))
+}
+
+fn process_struct(
+    strct: &StructInfo,
+    inits: &mut Vec<InitInfo>,
+    deinits: &mut Vec<DeinitInfo>,
+    functions: &mut Vec<FunctionInfo>,
+    properties: &mut Vec<PropertyInfo>,
+) -> ObjcClass {
+    let name = objc_name(&strct.name);
+
+    // Only class structs have an instance to initialize.
+    let initializers = if strct.is_class {
+        take_associated(inits, &strct.name, |init| &init.name)
+            .iter()
+            .filter(|init| init.is_public)
+            .filter_map(|init| process_init(init, &strct.name))
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let deinit = take_associated(deinits, &strct.name, |deinit| &deinit.name)
+        .into_iter()
+        .next()
+        .map(|deinit| deinit.name);
+
+    let methods = take_functions(functions, &strct.name)
+        .iter()
+        .filter(|func| func.is_public)
+        .filter_map(|func| {
+            let is_static = func.is_static || !strct.is_class;
+            process_method(func, &strct.name, is_static)
+        })
+        .collect();
+
+    let properties = if strct.is_class {
+        take_associated(properties, &strct.name, |prop| &prop.name)
+            .iter()
+            .filter(|prop| prop.is_public)
+            .filter_map(|prop| process_property(prop, &strct.name))
+            .collect()
+    } else {
+        vec![]
+    };
+
+    ObjcClass {
+        name,
+        c_name: strct.name.clone(),
+        is_class: strct.is_class,
+        deinit,
+        initializers,
+        properties,
+        methods,
+    }
+}
+
+fn process_enum(
+    enm: &EnumInfo,
+    functions: &mut Vec<FunctionInfo>,
+    properties: &mut Vec<PropertyInfo>,
+) -> ObjcEnum {
+    let name = objc_name(&enm.name);
+
+    let variants = enm
+        .variants
+        .iter()
+        .map(|variant| ObjcEnumVariant {
+            name: format!("{name}{}", variant.name.to_upper_camel_case()),
+            value: variant.value.clone(),
+        })
+        .collect();
+
+    let mut c_functions = take_functions(functions, &enm.name)
+        .iter()
+        .filter(|func| func.is_public)
+        .filter_map(|func| {
+            let ret = (!func.return_type.is_unit()).then_some(&func.return_type);
+            process_c_function(&func.name, &func.params, ret)
+        })
+        .collect::<Vec<_>>();
+
+    // Properties take the enum value as their only parameter.
+    for prop in take_associated(properties, &enm.name, |prop| &prop.name) {
+        let param = ParamInfo {
+            name: "value".to_string(),
+            ty: object_type(&enm.name, true, false),
+        };
+
+        if let Some(func) = process_c_function(&prop.name, &[param], Some(&prop.return_type)) {
+            c_functions.push(func);
+        }
+    }
+
+    ObjcEnum {
+        name,
+        value_type: ObjcType(c_type(&enm.value_type).unwrap_or_else(|| "NSInteger".to_string())),
+        variants,
+        functions: c_functions,
+    }
+}
+
+fn process_init(init: &InitInfo, object_name: &str) -> Option<ObjcMethod> {
+    // E.g. `TWFooCreateWithString` -> `initWithString`.
+    let name = transform_name(&init.name, Some(object_name), NameStyle::LowerCamelCase);
+    let name = match name.strip_prefix("create") {
+        Some("") => match init.params.first() {
+            Some(param) => format!("initWith{}", param.name.to_upper_camel_case()),
+            None => "init".to_string(),
+        },
+        Some(rest) => format!("init{rest}"),
+        None => format!("initWith{}", name.to_upper_camel_case()),
+    };
+
+    let ret = if init.is_nullable {
+        "nullable instancetype"
+    } else {
+        "instancetype"
+    };
+
+    let mut call = Call::default();
+    let mut selector = vec![];
+    for param in &init.params {
+        selector.push(call.push_param(param)?);
+    }
+
+    let declaration = format!("- ({ret}){}", join_selector(&name, &selector));
+    let ty = object_type(object_name, false, init.is_nullable);
+    let body = call.finish(&init.name, Some(&ty), Return::Init)?;
+
+    Some(ObjcMethod { declaration, body })
+}
+
+fn process_method(func: &FunctionInfo, object_name: &str, is_static: bool) -> Option<ObjcMethod> {
+    let mut call = Call::default();
+    let mut selector = vec![];
+    for param in &func.params {
+        // The 'self' parameter is the wrapped pointer of the instance.
+        if !is_static && is_self_param(param, object_name) {
+            call.args.push("self.rawValue".to_string());
+            continue;
+        }
+
+        selector.push(call.push_param(param)?);
+    }
+
+    let ret = (!func.return_type.is_unit()).then_some(&func.return_type);
+    let declaration = format!(
+        "{} ({}){}",
+        if is_static { "+" } else { "-" },
+        return_type(ret)?,
+        join_selector(
+            &transform_name(&func.name, Some(object_name), NameStyle::LowerCamelCase),
+            &selector
+        )
+    );
+    let body = call.finish(&func.name, ret, Return::Value)?;
+
+    Some(ObjcMethod { declaration, body })
+}
+
+fn process_property(prop: &PropertyInfo, object_name: &str) -> Option<ObjcProperty> {
+    let name = transform_name(&prop.name, Some(object_name), NameStyle::LowerCamelCase);
+    let ty = ObjcType::try_from(&prop.return_type).ok()?;
+
+    let attributes = if prop.return_type.is_nullable && ty.is_pointer() {
+        "nonatomic, readonly, nullable"
+    } else {
+        "nonatomic, readonly"
+    };
+
+    let mut call = Call::default();
+    call.args.push("self.rawValue".to_string());
+
+    Some(ObjcProperty {
+        declaration: format!("@property ({attributes}) {}", declare(&ty.0, &name)),
+        getter: ObjcMethod {
+            declaration: format!("- ({}){name}", return_type(Some(&prop.return_type))?),
+            body: call.finish(&prop.name, Some(&prop.return_type), Return::Value)?,
+        },
+    })
+}
+
+// E.g. `int32_t MainEnumIsSecond(MainEnum value)`.
+fn process_c_function(
+    c_name: &str,
+    params: &[ParamInfo],
+    ret: Option<&TypeInfo>,
+) -> Option<ObjcMethod> {
+    let mut call = Call::default();
+    let mut declared = vec![];
+    for param in params {
+        let param = call.push_param(param)?;
+        let ty = if param.is_nullable && param.ty.is_pointer() {
+            format!("{}_Nullable ", param.ty)
+        } else {
+            param.ty.to_string()
+        };
+        declared.push(declare(&ty, &param.name));
+    }
+
+    let ret_type = match ret {
+        Some(ty) => {
+            let objc_type = ObjcType::try_from(ty).ok()?;
+            if ty.is_nullable && objc_type.is_pointer() {
+                format!("{objc_type}_Nullable ")
+            } else {
+                objc_type.0
+            }
+        }
+        None => "void".to_string(),
+    };
+
+    let name = c_name.strip_prefix("TW").unwrap_or(c_name);
+    let declaration = format!("{}({})", declare(&ret_type, name), declared.join(", "));
+    let body = call.finish(c_name, ret, Return::Value)?;
+
+    Some(ObjcMethod { declaration, body })
+}
+
+#[derive(Debug, Clone)]
+struct ObjcParam {
+    name: String,
+    ty: ObjcType,
+    is_nullable: bool,
+}
+
+// How the result of the C function is returned.
+enum Return {
+    // Initializes `self` with the struct pointer.
+    Init,
+    // Converts the result into the Objective-C type.
+    Value,
+}
+
+// The statements calling a C function: the arguments are converted first, and
+// the temporary `TWString`s and `TWData`s are deleted after the call.
+#[derive(Debug, Clone, Default)]
+struct Call {
+    setup: Vec<String>,
+    args: Vec<String>,
+    cleanup: Vec<String>,
+}
+
+impl Call {
+    // Returns `None` if the type of the parameter is not supported.
+    fn push_param(&mut self, param: &ParamInfo) -> Option<ObjcParam> {
+        let ty = ObjcType::try_from(&param.ty).ok()?;
+        let name = param.name.clone();
+        let is_nullable = param.ty.is_nullable;
+
+        let arg = match &param.ty.variant {
+            TypeVariant::String | TypeVariant::Data => {
+                let (c_type, create, delete) = match param.ty.variant {
+                    TypeVariant::String => (
+                        "TWString",
+                        format!("TWStringCreateWithUTF8Bytes([{name} UTF8String])"),
+                        "TWStringDelete",
+                    ),
+                    _ => (
+                        "TWData",
+                        format!("TWDataCreateWithBytes({name}.bytes, {name}.length)"),
+                        "TWDataDelete",
+                    ),
+                };
+
+                if is_nullable {
+                    self.setup.push(format!(
+                        "{c_type} *{name}TW = {name} != nil ? {create} : NULL;"
+                    ));
+                    self.cleanup.push(format!("if ({name}TW != NULL) {{"));
+                    self.cleanup.push(format!("    {delete}({name}TW);"));
+                    self.cleanup.push("}".to_string());
+                } else {
+                    self.setup.push(format!("{c_type} *{name}TW = {create};"));
+                    self.cleanup.push(format!("{delete}({name}TW);"));
+                }
+
+                format!("{name}TW")
+            }
+            // Messaging `nil` returns `NULL`, so nullable structs need no
+            // special treatment.
+            TypeVariant::Struct(_) => format!("{name}.rawValue"),
+            TypeVariant::Enum(c_name) => format!("(enum {c_name}){name}"),
+            _ => name.clone(),
+        };
+        self.args.push(arg);
+
+        Some(ObjcParam {
+            name,
+            ty,
+            is_nullable,
+        })
+    }
+
+    // Returns `None` if the return type is not supported.
+    fn finish(self, c_name: &str, ret: Option<&TypeInfo>, how: Return) -> Option<Vec<String>> {
+        let call = format!("{c_name}({})", self.args.join(", "));
+        let mut body = self.setup;
+
+        let Some(ty) = ret else {
+            body.push(format!("{call};"));
+            body.extend(self.cleanup);
+            return Some(body);
+        };
+
+        let result_type = match (&ty.variant, ty.pointer_depth) {
+            (TypeVariant::String, 1) => "TWString *".to_string(),
+            (TypeVariant::Data, 1) => "TWData *".to_string(),
+            (TypeVariant::Struct(n), 1) => format!("struct {n} *"),
+            (TypeVariant::Enum(n), 0) => format!("enum {n}"),
+            (TypeVariant::Void, 1) => "void *".to_string(),
+            (variant, 0) => c_type(variant)?,
+            _ => return None,
+        };
+        body.push(format!("{} = {call};", declare(&result_type, "result")));
+        body.extend(self.cleanup);
+
+        let is_object = matches!(
+            (&ty.variant, ty.pointer_depth),
+            (
+                TypeVariant::String | TypeVariant::Data | TypeVariant::Struct(_),
+                1
+            )
+        );
+        if ty.is_nullable && is_object {
+            body.push("if (result == NULL) {".to_string());
+            body.push("    return nil;".to_string());
+            body.push("}".to_string());
+        }
+
+        match (&ty.variant, how) {
+            (TypeVariant::String, _) => {
+                body.push(
+                    "NSString *output = [NSString stringWithUTF8String:TWStringUTF8Bytes(result)];"
+                        .to_string(),
+                );
+                body.push("TWStringDelete(result);".to_string());
+                body.push("return output;".to_string());
+            }
+            (TypeVariant::Data, _) => {
+                body.push(
+                    "NSData *output = [NSData dataWithBytes:TWDataBytes(result) length:TWDataSize(result)];"
+                        .to_string(),
+                );
+                body.push("TWDataDelete(result);".to_string());
+                body.push("return output;".to_string());
+            }
+            (TypeVariant::Struct(_), Return::Init) => {
+                body.push("return [self initWithRawValue:result];".to_string())
+            }
+            (TypeVariant::Struct(n), Return::Value) => body.push(format!(
+                "return [[{} alloc] initWithRawValue:result];",
+                objc_name(n)
+            )),
+            (TypeVariant::Enum(n), _) => body.push(format!("return ({})result;", objc_name(n))),
+            _ => body.push("return result;".to_string()),
+        }
+
+        Some(body)
+    }
+}
+
+// E.g. `nullable NSString *`, or `void` if the function returns nothing.
+fn return_type(ret: Option<&TypeInfo>) -> Option<String> {
+    let Some(ty) = ret else {
+        return Some("void".to_string());
+    };
+
+    let objc_type = ObjcType::try_from(ty).ok()?;
+    if ty.is_nullable && objc_type.is_pointer() {
+        Some(format!("nullable {objc_type}"))
+    } else {
+        Some(objc_type.0)
+    }
+}
+
+// E.g. `sign:(NSData *)data kind:(MainEnum)kind`.
+fn join_selector(name: &str, params: &[ObjcParam]) -> String {
+    let mut selector = name.to_string();
+
+    for (idx, param) in params.iter().enumerate() {
+        let label = if idx == 0 { "" } else { &param.name };
+        let ty = if param.is_nullable && param.ty.is_pointer() {
+            format!("nullable {}", param.ty)
+        } else {
+            param.ty.to_string()
+        };
+
+        if idx > 0 {
+            selector.push(' ');
+        }
+        selector.push_str(&format!("{label}:({ty}){}", param.name));
+    }
+
+    selector
+}
+
+// E.g. `NSString *name` or `BOOL name`.
+fn declare(ty: &str, name: &str) -> String {
+    if ty.ends_with('*') || ty.ends_with(' ') {
+        format!("{ty}{name}")
+    } else {
+        format!("{ty} {name}")
+    }
+}
+
+// We strip the "TW" prefix for Objective-C representations of structs/enums.
+fn objc_name(name: &str) -> String {
+    name.strip_prefix("TW").unwrap_or(name).to_string()
+}
+
+// Returns the C name of primitive types, e.g. `uint32_t`.
+fn c_type(variant: &TypeVariant) -> Option<String> {
+    let res = match variant {
+        TypeVariant::Bool => "bool",
+        TypeVariant::Char => "char",
+        TypeVariant::ShortInt => "short",
+        TypeVariant::Int => "int",
+        TypeVariant::UnsignedInt => "unsigned int",
+        TypeVariant::LongInt => "long",
+        TypeVariant::UnsignedLongInt => "unsigned long",
+        TypeVariant::Float => "float",
+        TypeVariant::Double => "double",
+        TypeVariant::SizeT => "size_t",
+        TypeVariant::IntPtr => "intptr_t",
+        TypeVariant::Int8T => "int8_t",
+        TypeVariant::Int16T => "int16_t",
+        TypeVariant::Int32T => "int32_t",
+        TypeVariant::Int64T => "int64_t",
+        TypeVariant::UInt8T => "uint8_t",
+        TypeVariant::UInt16T => "uint16_t",
+        TypeVariant::UInt32T => "uint32_t",
+        TypeVariant::UInt64T => "uint64_t",
+        TypeVariant::Unknown(raw) => return Some(raw.clone()),
+        _ => return None,
+    };
+
+    Some(res.to_string())
+}
@@ -0,0 +1,220 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::c_functions;
+use crate::manifest::{EnumInfo, FileInfo, StructInfo, TypeInfo, TypeVariant};
+use crate::Result;
+use handlebars::Handlebars;
+use heck::{ToShoutySnakeCase, ToSnakeCase};
+use std::fmt::Display;
+
+const FILE_TEMPLATE: &str = include_str!("templates/file.hbs");
+
+/// Represents a protobuf message, for a struct.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtoMessage {
+    name: String,
+    fields: Vec<ProtoField>,
+}
+
+/// Represents a message field, numbered in declaration order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtoField {
+    name: String,
+    // `None` if the field is not representable, e.g. function pointers.
+    #[serde(rename = "type")]
+    field_type: Option<ProtoType>,
+    number: usize,
+}
+
+/// Represents a protobuf enum, which always has a zero value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtoEnum {
+    name: String,
+    variants: Vec<ProtoEnumVariant>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtoEnumVariant {
+    name: String,
+    value: String,
+}
+
+// Wrapper around a valid protobuf field type, including the `repeated` label.
+// Meant to be used as `<ProtoType as TryFrom<&TypeInfo>>::try_from(...)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtoType(String);
+
+impl Display for ProtoType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Convert the `TypeVariant` into the appropriate protobuf type. Pointers are
+/// irrelevant for the schema, hence ignored.
+impl TryFrom<TypeVariant> for ProtoType {
+    type Error = ();
+
+    fn try_from(value: TypeVariant) -> std::result::Result<Self, Self::Error> {
+        let res = match value {
+            TypeVariant::Bool => "bool".to_string(),
+            TypeVariant::Char
+            | TypeVariant::ShortInt
+            | TypeVariant::Int
+            | TypeVariant::Int8T
+            | TypeVariant::Int16T
+            | TypeVariant::Int32T => "int32".to_string(),
+            TypeVariant::UnsignedInt
+            | TypeVariant::UInt8T
+            | TypeVariant::UInt16T
+            | TypeVariant::UInt32T => "uint32".to_string(),
+            TypeVariant::LongInt | TypeVariant::Int64T | TypeVariant::IntPtr => "int64".to_string(),
+            TypeVariant::UnsignedLongInt | TypeVariant::UInt64T | TypeVariant::SizeT => {
+                "uint64".to_string()
+            }
+            TypeVariant::Float => "float".to_string(),
+            TypeVariant::Double => "double".to_string(),
+            TypeVariant::String | TypeVariant::CString => "string".to_string(),
+            TypeVariant::Data => "bytes".to_string(),
+            // Byte arrays, such as `uint8_t key[32]`, are bytes.
+            TypeVariant::Array { element, .. } => match *element {
+                TypeVariant::UInt8T | TypeVariant::Int8T | TypeVariant::Char => "bytes".to_string(),
+                element => format!("repeated {}", ProtoType::try_from(element)?),
+            },
+            TypeVariant::Struct(n) | TypeVariant::Enum(n) => {
+                // We strip the "TW" prefix, the messages are part of the `TW`
+                // package instead.
+                n.strip_prefix("TW").map(|n| n.to_string()).unwrap_or(n)
+            }
+            TypeVariant::Void | TypeVariant::FunctionPointer { .. } | TypeVariant::Unknown(_) => {
+                return Err(())
+            }
+        };
+
+        Ok(ProtoType(res))
+    }
+}
+
+impl TryFrom<&TypeInfo> for ProtoType {
+    type Error = ();
+
+    fn try_from(ty: &TypeInfo) -> std::result::Result<Self, Self::Error> {
+        // Pointers to pointers, such as out-parameters, are not representable.
+        if ty.pointer_depth > 1 {
+            return Err(());
+        }
+
+        ProtoType::try_from(ty.variant.clone())
+    }
+}
+
+/// Generates a protobuf schema with a message for every public struct and an
+/// enum for every public enum of the given file. Functions are skipped, but
+/// listed in a trailing comment.
+pub fn generate_proto(file: &FileInfo) -> Result<String> {
+    let mut engine = Handlebars::new();
+    // Unmatched variables should result in an error.
+    engine.set_strict_mode(true);
+    // Escaping is not required for protobuf schemas.
+    engine.register_escape_fn(handlebars::no_escape);
+
+    engine.register_template_string("file", FILE_TEMPLATE)?;
+
+    let messages = file
+        .structs
+        .iter()
+        .filter(|strct| strct.is_public)
+        .map(process_struct)
+        .collect::<Vec<_>>();
+
+    let enums = file
+        .enums
+        .iter()
+        .filter(|enm| enm.is_public)
+        .map(process_enum)
+        .collect::<Vec<_>>();
+
+    let functions = c_functions(file)
+        .into_iter()
+        .map(|func| func.name)
+        .collect::<Vec<_>>();
+
+    let out = engine.render(
+        "file",
+        &serde_json::json!({
+            "current_year": crate::current_year(),
+            "enums": enums,
+            "messages": messages,
+            "functions": functions,
+        }),
+    )?;
+
+    Ok(out)
+}
+
+fn process_struct(strct: &StructInfo) -> ProtoMessage {
+    ProtoMessage {
+        name: ProtoType::try_from(TypeVariant::Struct(strct.name.clone()))
+            .map(|ty| ty.0)
+            .unwrap_or_default(),
+        fields: strct
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| ProtoField {
+                name: field.name.to_snake_case(),
+                field_type: ProtoType::try_from(&field.ty).ok(),
+                number: idx + 1,
+            })
+            .collect(),
+    }
+}
+
+fn process_enum(enm: &EnumInfo) -> ProtoEnum {
+    let name = ProtoType::try_from(TypeVariant::Enum(enm.name.clone()))
+        .map(|ty| ty.0)
+        .unwrap_or_default();
+
+    // Enum values are scoped to the package, hence prefixed with the enum
+    // name, e.g. `CURVE_ED25519`.
+    let prefix = name.to_shouty_snake_case();
+
+    let mut variants = enm
+        .variants
+        .iter()
+        .map(|variant| ProtoEnumVariant {
+            name: format!("{prefix}_{}", variant.name.to_shouty_snake_case()),
+            value: variant.value.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    // The first value of a proto3 enum must be zero.
+    match variants.iter().position(|variant| is_zero(&variant.value)) {
+        Some(idx) => {
+            let zero = variants.remove(idx);
+            variants.insert(0, zero);
+        }
+        None => variants.insert(
+            0,
+            ProtoEnumVariant {
+                name: format!("{prefix}_UNSPECIFIED"),
+                value: "0".to_string(),
+            },
+        ),
+    }
+
+    ProtoEnum { name, variants }
+}
+
+fn is_zero(value: &str) -> bool {
+    let digits = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+
+    !digits.is_empty() && digits.chars().all(|c| c == '0')
+}
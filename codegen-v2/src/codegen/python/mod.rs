@@ -0,0 +1,258 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::c_functions;
+use crate::manifest::{EnumInfo, FileInfo, StructInfo, TypeInfo, TypeVariant};
+use crate::Result;
+use handlebars::Handlebars;
+use heck::ToShoutySnakeCase;
+use std::fmt::Display;
+
+const FILE_TEMPLATE: &str = include_str!("templates/file.hbs");
+
+/// Represents a `ctypes.Structure` subclass, for structs with fields. Opaque
+/// structs are passed on as `ctypes.c_void_p` handles instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PyStruct {
+    name: String,
+    fields: Vec<PyParam>,
+}
+
+/// Represents an `IntEnum` subclass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PyEnum {
+    name: String,
+    variants: Vec<PyEnumVariant>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PyEnumVariant {
+    name: String,
+    value: String,
+}
+
+/// Represents a function whose `argtypes` and `restype` are declared when
+/// loading the library.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PyFunction {
+    name: String,
+    params: Vec<PyParam>,
+    #[serde(rename = "return")]
+    return_type: PyType,
+}
+
+/// Represents a function parameter or a struct field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PyParam {
+    name: String,
+    #[serde(rename = "type")]
+    param_type: PyType,
+    // Bitfields are declared as `(name, type, width)` by `ctypes`.
+    bit_width: Option<u8>,
+}
+
+// Wrapper around a valid `ctypes` type, such as `ctypes.c_uint32`. Meant to
+// be used as `PyType::new(...)`, since enums are passed on as their value
+// type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PyType(String);
+
+impl Display for PyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PyType {
+    /// Converts the type, looking up the value types of enums in the given
+    /// file. Enums of other files are expected to be `uint32_t` based, like
+    /// most wallet-core enums.
+    fn new(ty: &TypeInfo, file: &FileInfo) -> Self {
+        let res = match &ty.variant {
+            // `const char*` is a C string, other pointers are opaque handles.
+            TypeVariant::Char if ty.pointer_depth == 1 => "ctypes.c_char_p".to_string(),
+            TypeVariant::CString if ty.pointer_depth == 0 => "ctypes.c_char_p".to_string(),
+            _ if ty.is_pointer() => "ctypes.c_void_p".to_string(),
+            TypeVariant::Void => "None".to_string(),
+            TypeVariant::Enum(name) => {
+                let value_type = file
+                    .enums
+                    .iter()
+                    .find(|enm| &enm.name == name)
+                    .map(|enm| enm.value_type.clone())
+                    .unwrap_or(TypeVariant::UInt32T);
+
+                PyType::new(&value_type_info(value_type), file).0
+            }
+            TypeVariant::Array { element, len } => {
+                let element = PyType::new(&value_type_info(*element.clone()), file);
+                match len {
+                    Some(len) => format!("{element} * {len}"),
+                    None => format!("ctypes.POINTER({element})"),
+                }
+            }
+            // E.g. `ctypes.CFUNCTYPE(None, ctypes.c_void_p)`
+            TypeVariant::FunctionPointer { ret, params } => {
+                let types = std::iter::once(ret.as_ref())
+                    .chain(params)
+                    .map(|ty| PyType::new(ty, file).0)
+                    .collect::<Vec<_>>();
+
+                format!("ctypes.CFUNCTYPE({})", types.join(", "))
+            }
+            // Structs passed by value are declared by this file.
+            TypeVariant::Struct(name) => name.clone(),
+            TypeVariant::Unknown(raw) => raw.clone(),
+            variant => format!("ctypes.{}", primitive_name(variant)),
+        };
+
+        PyType(res)
+    }
+}
+
+/// Generates a Python module of `ctypes` bindings for all public structs,
+/// enums and functions of the given file. The functions are available once
+/// the native library is loaded via `load(path)`; variadic functions are
+/// skipped.
+pub fn generate_python(file: &FileInfo) -> Result<String> {
+    let mut engine = Handlebars::new();
+    // Unmatched variables should result in an error.
+    engine.set_strict_mode(true);
+    // Expressions such as `ctypes.c_uint8 * 32` must not be HTML-escaped.
+    engine.register_escape_fn(handlebars::no_escape);
+
+    engine.register_template_string("file", FILE_TEMPLATE)?;
+
+    let structs = file
+        .structs
+        .iter()
+        .filter(|strct| strct.is_public && !strct.fields.is_empty())
+        .map(|strct| process_struct(strct, file))
+        .collect::<Vec<_>>();
+
+    let enums = file
+        .enums
+        .iter()
+        .filter(|enm| enm.is_public)
+        .map(process_enum)
+        .collect::<Vec<_>>();
+
+    let functions = c_functions(file)
+        .into_iter()
+        .filter(|func| !func.is_variadic)
+        .map(|func| PyFunction {
+            name: func.name,
+            params: func
+                .params
+                .iter()
+                .map(|param| PyParam {
+                    name: param_name(&param.name),
+                    param_type: PyType::new(&param.ty, file),
+                    bit_width: None,
+                })
+                .collect(),
+            return_type: match &func.return_type {
+                Some(ty) => PyType::new(ty, file),
+                None => PyType("None".to_string()),
+            },
+        })
+        .collect::<Vec<_>>();
+
+    let out = engine.render(
+        "file",
+        &serde_json::json!({
+            "current_year": crate::current_year(),
+            "enums": enums,
+            "structs": structs,
+            "functions": functions,
+        }),
+    )?;
+
+    Ok(out)
+}
+
+fn process_struct(strct: &StructInfo, file: &FileInfo) -> PyStruct {
+    PyStruct {
+        name: strct.name.clone(),
+        fields: strct
+            .fields
+            .iter()
+            .map(|field| PyParam {
+                name: field.name.clone(),
+                param_type: PyType::new(&field.ty, file),
+                bit_width: field.bit_width,
+            })
+            .collect(),
+    }
+}
+
+fn process_enum(enm: &EnumInfo) -> PyEnum {
+    PyEnum {
+        name: enm.name.clone(),
+        variants: enm
+            .variants
+            .iter()
+            .map(|variant| PyEnumVariant {
+                name: variant.name.to_shouty_snake_case(),
+                value: variant.value.clone(),
+            })
+            .collect(),
+    }
+}
+
+// Wraps the variant into a non-pointer type.
+fn value_type_info(variant: TypeVariant) -> TypeInfo {
+    TypeInfo {
+        variant,
+        is_constant: false,
+        is_nullable: false,
+        pointer_depth: 0,
+        is_pointer_const: false,
+    }
+}
+
+// Returns the `ctypes` name of primitive C types, e.g. `c_uint32`.
+fn primitive_name(variant: &TypeVariant) -> &'static str {
+    match variant {
+        TypeVariant::Bool => "c_bool",
+        TypeVariant::Char => "c_char",
+        TypeVariant::ShortInt => "c_short",
+        TypeVariant::Int => "c_int",
+        TypeVariant::UnsignedInt => "c_uint",
+        TypeVariant::LongInt => "c_long",
+        TypeVariant::UnsignedLongInt => "c_ulong",
+        TypeVariant::Float => "c_float",
+        TypeVariant::Double => "c_double",
+        TypeVariant::SizeT => "c_size_t",
+        TypeVariant::IntPtr => "c_ssize_t",
+        TypeVariant::Int8T => "c_int8",
+        TypeVariant::Int16T => "c_int16",
+        TypeVariant::Int32T => "c_int32",
+        TypeVariant::Int64T => "c_int64",
+        TypeVariant::UInt8T => "c_uint8",
+        TypeVariant::UInt16T => "c_uint16",
+        TypeVariant::UInt32T => "c_uint32",
+        TypeVariant::UInt64T => "c_uint64",
+        // `TWString` and `TWData` are opaque handles.
+        _ => "c_void_p",
+    }
+}
+
+// Escapes parameter names which are Python keywords, such as `from`.
+fn param_name(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is",
+        "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with",
+        "yield",
+    ];
+
+    if KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
@@ -0,0 +1,281 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::{c_functions, reject_bitfields, TargetLanguage};
+use crate::manifest::{EnumInfo, FileInfo, ParamInfo, StructInfo, TypeInfo, TypeVariant};
+use crate::{Error, Result};
+use handlebars::Handlebars;
+use heck::ToUpperCamelCase;
+use std::fmt::Display;
+
+const FILE_TEMPLATE: &str = include_str!("templates/file.hbs");
+
+/// Represents a `#[repr(C)]` struct. Structs without fields are opaque.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustStruct {
+    name: String,
+    fields: Vec<RustParam>,
+}
+
+/// Represents a fieldless enum with an explicit integer representation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustEnum {
+    name: String,
+    repr: RustType,
+    variants: Vec<RustEnumVariant>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustEnumVariant {
+    name: String,
+    value: String,
+}
+
+/// Represents a function declaration of the `extern "C"` block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustFunction {
+    name: String,
+    params: Vec<RustParam>,
+    // `None` if the function returns nothing.
+    #[serde(rename = "return")]
+    return_type: Option<RustType>,
+    is_variadic: bool,
+}
+
+/// Represents a function parameter or a struct field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustParam {
+    name: String,
+    #[serde(rename = "type")]
+    param_type: RustType,
+}
+
+// Wrapper around a valid Rust FFI type. Meant to be used as
+// `<RustType as From<&TypeInfo>>::from(...)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustType(String);
+
+impl Display for RustType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Convert the `TypeVariant` into the appropriate Rust FFI type, ignoring any
+/// pointer indirection.
+impl From<TypeVariant> for RustType {
+    fn from(value: TypeVariant) -> Self {
+        let res = match value {
+            TypeVariant::Void => "c_void".to_string(),
+            TypeVariant::Bool => "bool".to_string(),
+            TypeVariant::Char => "c_char".to_string(),
+            TypeVariant::ShortInt => "c_short".to_string(),
+            TypeVariant::Int => "c_int".to_string(),
+            TypeVariant::UnsignedInt => "c_uint".to_string(),
+            TypeVariant::LongInt => "c_long".to_string(),
+            TypeVariant::UnsignedLongInt => "c_ulong".to_string(),
+            TypeVariant::Float => "f32".to_string(),
+            TypeVariant::Double => "f64".to_string(),
+            TypeVariant::SizeT => "usize".to_string(),
+            TypeVariant::IntPtr => "isize".to_string(),
+            TypeVariant::Int8T => "i8".to_string(),
+            TypeVariant::Int16T => "i16".to_string(),
+            TypeVariant::Int32T => "i32".to_string(),
+            TypeVariant::Int64T => "i64".to_string(),
+            TypeVariant::UInt8T => "u8".to_string(),
+            TypeVariant::UInt16T => "u16".to_string(),
+            TypeVariant::UInt32T => "u32".to_string(),
+            TypeVariant::UInt64T => "u64".to_string(),
+            // `TWString` and `TWData` are declared as `const void` in C.
+            TypeVariant::String | TypeVariant::Data => "c_void".to_string(),
+            TypeVariant::CString => "*const c_char".to_string(),
+            TypeVariant::Array { element, len } => match len {
+                Some(len) => format!("[{}; {len}]", RustType::from(*element)),
+                None => format!("*const {}", RustType::from(*element)),
+            },
+            // E.g. `unsafe extern "C" fn(*const c_void)`
+            TypeVariant::FunctionPointer { ret, params } => {
+                let params = params
+                    .iter()
+                    .map(|ty| RustType::from(ty).0)
+                    .collect::<Vec<_>>();
+                let ret = if ret.is_unit() {
+                    String::new()
+                } else {
+                    format!(" -> {}", RustType::from(ret.as_ref()))
+                };
+
+                format!("unsafe extern \"C\" fn({}){ret}", params.join(", "))
+            }
+            // Unknown types keep their raw C keyword.
+            TypeVariant::Unknown(raw) => raw,
+            // Keep the C names, as required for linking.
+            TypeVariant::Struct(n) | TypeVariant::Enum(n) => n,
+        };
+
+        RustType(res)
+    }
+}
+
+impl From<&TypeInfo> for RustType {
+    fn from(ty: &TypeInfo) -> Self {
+        let mut res = RustType::from(ty.variant.clone()).0;
+
+        // Function pointers cannot be null in Rust.
+        if matches!(ty.variant, TypeVariant::FunctionPointer { .. }) && ty.is_nullable {
+            res = format!("Option<{res}>");
+        }
+
+        // Only the pointee is qualified as constant, e.g. `const TWData**` is
+        // `*mut *const c_void`.
+        for depth in 0..ty.pointer_depth {
+            let qualifier = if depth == 0 && ty.is_constant {
+                "*const"
+            } else {
+                "*mut"
+            };
+
+            res = format!("{qualifier} {res}");
+        }
+
+        RustType(res)
+    }
+}
+
+impl From<&ParamInfo> for RustParam {
+    fn from(param: &ParamInfo) -> Self {
+        RustParam {
+            name: param_name(&param.name),
+            param_type: RustType::from(&param.ty),
+        }
+    }
+}
+
+/// Generates the Rust FFI declarations for all public structs, enums and
+/// functions of the given file. The generated module expects the types of
+/// other files to be in scope of its parent module.
+pub fn generate_rust(file: &FileInfo) -> Result<String> {
+    let mut engine = Handlebars::new();
+    // Unmatched variables should result in an error.
+    engine.set_strict_mode(true);
+    // Pointer types such as `*const c_void` must not be HTML-escaped.
+    engine.register_escape_fn(handlebars::no_escape);
+
+    engine.register_template_string("file", FILE_TEMPLATE)?;
+
+    let structs = file
+        .structs
+        .iter()
+        .filter(|strct| strct.is_public)
+        .map(process_struct)
+        .collect::<Result<Vec<_>>>()?;
+
+    let enums = file
+        .enums
+        .iter()
+        .filter(|enm| enm.is_public)
+        .map(process_enum)
+        .collect::<Result<Vec<_>>>()?;
+
+    let functions = c_functions(file)
+        .into_iter()
+        .map(|func| RustFunction {
+            name: func.name,
+            params: func.params.iter().map(RustParam::from).collect(),
+            return_type: func.return_type.as_ref().map(RustType::from),
+            is_variadic: func.is_variadic,
+        })
+        .collect::<Vec<_>>();
+
+    let out = engine.render(
+        "file",
+        &serde_json::json!({
+            "current_year": crate::current_year(),
+            "enums": enums,
+            "structs": structs,
+            "functions": functions,
+        }),
+    )?;
+
+    Ok(out)
+}
+
+fn process_struct(strct: &StructInfo) -> Result<RustStruct> {
+    reject_bitfields(strct, TargetLanguage::Rust)?;
+
+    Ok(RustStruct {
+        name: strct.name.clone(),
+        fields: strct
+            .fields
+            .iter()
+            .map(|field| RustParam {
+                name: param_name(&field.name),
+                param_type: RustType::from(&field.ty),
+            })
+            .collect(),
+    })
+}
+
+fn process_enum(enm: &EnumInfo) -> Result<RustEnum> {
+    Ok(RustEnum {
+        name: enm.name.clone(),
+        repr: enum_repr(enm)?,
+        variants: enm
+            .variants
+            .iter()
+            .map(|variant| RustEnumVariant {
+                name: variant.name.to_upper_camel_case(),
+                value: variant.value.clone(),
+            })
+            .collect(),
+    })
+}
+
+// `#[repr(...)]` only accepts primitive integers, rather than the `c_int`
+// aliases used elsewhere. The size of `long` and `char`'s signedness depend on
+// the target, hence neither has a fixed representation.
+fn enum_repr(enm: &EnumInfo) -> Result<RustType> {
+    let repr = match &enm.value_type {
+        TypeVariant::ShortInt => "i16",
+        TypeVariant::Int => "i32",
+        TypeVariant::UnsignedInt => "u32",
+        TypeVariant::SizeT => "usize",
+        TypeVariant::IntPtr => "isize",
+        TypeVariant::Int8T => "i8",
+        TypeVariant::Int16T => "i16",
+        TypeVariant::Int32T => "i32",
+        TypeVariant::Int64T => "i64",
+        TypeVariant::UInt8T => "u8",
+        TypeVariant::UInt16T => "u16",
+        TypeVariant::UInt32T => "u32",
+        TypeVariant::UInt64T => "u64",
+        other => {
+            return Err(Error::BadType(format!(
+                "{} is backed by {other:?}, which has no fixed-size Rust representation",
+                enm.name
+            )))
+        }
+    };
+
+    Ok(RustType(repr.to_string()))
+}
+
+// Escapes parameter names which are Rust keywords, such as `type` or `fn`.
+pub(crate) fn param_name(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type",
+        "unsafe", "use", "where", "while", "yield",
+    ];
+
+    match name {
+        // These cannot be used as raw identifiers.
+        "self" | "Self" | "super" | "crate" => format!("{name}_"),
+        _ if KEYWORDS.contains(&name) => format!("r#{name}"),
+        _ => name.to_string(),
+    }
+}
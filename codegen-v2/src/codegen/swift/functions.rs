@@ -5,8 +5,8 @@
 // file LICENSE at the root of the source code distribution tree.
 
 use super::*;
+use crate::codegen::{transform_name, NameStyle};
 use crate::manifest::{FunctionInfo, TypeVariant};
-use heck::ToLowerCamelCase;
 
 /// This function checks each function and determines whether there's an
 /// association with the passed on object (struct or enum), based on common name
@@ -22,12 +22,17 @@ pub(super) fn process_methods(
     let mut skipped_funcs = vec![];
 
     for func in functions {
-        if !func.name.starts_with(object.name()) {
+        if !func.is_associated_with(object.name()) {
             // Function is not assciated with the object.
             skipped_funcs.push(func);
             continue;
         }
 
+        // Swift cannot call variadic C functions.
+        if func.is_variadic {
+            continue;
+        }
+
         let mut ops = vec![];
 
         // Initalize the 'self' type, which is then passed on to the underlying
@@ -110,12 +115,8 @@ pub(super) fn process_methods(
         };
 
         // Prettify name, remove object name prefix from this property.
-        let pretty_name = func
-            .name
-            .strip_prefix(object.name())
-            // Panicing implies bug, checked at the start of the loop.
-            .unwrap()
-            .to_lower_camel_case();
+        let pretty_name =
+            transform_name(&func.name, Some(object.name()), NameStyle::LowerCamelCase);
 
         // Special handling: some functions do not follow standard camelCase
         // convention.
@@ -146,10 +147,11 @@ pub(super) fn process_methods(
             name: pretty_name,
             is_public: func.is_public,
             is_static: func.is_static,
+            deprecated: SwiftDeprecation::new(func.is_deprecated, func.deprecation_note),
             operations: ops,
             params,
             return_type,
-            comments: vec![],
+            comments: func.comments,
         });
     }
 
@@ -85,7 +85,7 @@ pub(super) fn process_inits(
             is_public: init.is_public,
             params,
             operations: ops,
-            comments: vec![],
+            comments: init.comments,
         });
     }
 
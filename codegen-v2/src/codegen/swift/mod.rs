@@ -21,8 +21,8 @@ mod render;
 
 // Re-exports
 pub use self::render::{
-    generate_swift_types, render_to_strings, GeneratedSwiftTypes, GeneratedSwiftTypesStrings,
-    RenderIntput,
+    generate_swift_types, render_to_files, render_to_strings, GeneratedSwiftTypes,
+    GeneratedSwiftTypesStrings, RenderIntput,
 };
 
 /// Represents a Swift struct or class.
@@ -31,6 +31,7 @@ pub struct SwiftStruct {
     name: String,
     is_class: bool,
     is_public: bool,
+    deprecated: Option<SwiftDeprecation>,
     init_instance: bool,
     superclasses: Vec<String>,
     eq_operator: Option<SwiftOperatorEquality>,
@@ -85,6 +86,7 @@ pub struct SwiftFunction {
     pub name: String,
     pub is_public: bool,
     pub is_static: bool,
+    pub deprecated: Option<SwiftDeprecation>,
     pub params: Vec<SwiftParam>,
     pub operations: Vec<SwiftOperation>,
     #[serde(rename = "return")]
@@ -186,6 +188,22 @@ pub struct SwiftProto {
     pub c_ffi_name: String,
 }
 
+/// Results in an `@available(*, deprecated)` attribute, with an optional
+/// message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwiftDeprecation {
+    pub message: Option<String>,
+}
+
+impl SwiftDeprecation {
+    fn new(is_deprecated: bool, note: Option<String>) -> Option<Self> {
+        is_deprecated.then(|| SwiftDeprecation {
+            // The message is rendered unescaped into a Swift string literal.
+            message: note.map(|note| note.replace('\\', "\\\\").replace('"', "\\\"")),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwiftOperatorEquality {
     pub c_ffi_name: String,
@@ -228,9 +246,11 @@ impl From<TypeVariant> for SwiftType {
             TypeVariant::Int => "Int32".to_string(),
             TypeVariant::UnsignedInt => "UInt32".to_string(),
             TypeVariant::LongInt => "Int64".to_string(),
+            TypeVariant::UnsignedLongInt => "UInt64".to_string(),
             TypeVariant::Float => "Float".to_string(),
             TypeVariant::Double => "Double".to_string(),
             TypeVariant::SizeT => "Int".to_string(),
+            TypeVariant::IntPtr => "Int".to_string(),
             TypeVariant::Int8T => "Int8".to_string(),
             TypeVariant::Int16T => "Int16".to_string(),
             TypeVariant::Int32T => "Int32".to_string(),
@@ -239,8 +259,27 @@ impl From<TypeVariant> for SwiftType {
             TypeVariant::UInt16T => "UInt16".to_string(),
             TypeVariant::UInt32T => "UInt32".to_string(),
             TypeVariant::UInt64T => "UInt64".to_string(),
-            TypeVariant::String => "String".to_string(),
+            TypeVariant::String | TypeVariant::CString => "String".to_string(),
             TypeVariant::Data => "Data".to_string(),
+            TypeVariant::Array { element, .. } => {
+                format!("[{}]", SwiftType::from(*element))
+            }
+            // E.g. `@convention(c) (Int32) -> Void`
+            TypeVariant::FunctionPointer { ret, params } => {
+                let params = params
+                    .into_iter()
+                    .map(|ty| SwiftType::from(ty.variant).0)
+                    .collect::<Vec<_>>();
+
+                format!(
+                    "@convention(c) ({}) -> {}",
+                    params.join(", "),
+                    SwiftType::from(ret.variant)
+                )
+            }
+            // Unknown types keep their raw keyword, so the generated code
+            // fails to compile rather than silently using a wrong type.
+            TypeVariant::Unknown(raw) => raw,
             TypeVariant::Struct(n) | TypeVariant::Enum(n) => {
                 // We strip the "TW" prefix for Swift representations of
                 // structs/enums.
@@ -5,8 +5,8 @@
 // file LICENSE at the root of the source code distribution tree.
 
 use super::*;
+use crate::codegen::{transform_name, NameStyle};
 use crate::manifest::PropertyInfo;
-use heck::ToLowerCamelCase;
 
 /// This function checks each property and determines whether there's an
 /// association with the passed on object (struct or enum), based on common name
@@ -65,12 +65,8 @@ pub(super) fn process_properties(
         ops.push(wrap_return(&prop.return_type));
 
         // Prettify name, remove object name prefix from this property.
-        let pretty_name = prop
-            .name
-            .strip_prefix(object.name())
-            // Panicing implies bug, checked at the start of the loop.
-            .unwrap()
-            .to_lower_camel_case();
+        let pretty_name =
+            transform_name(&prop.name, Some(object.name()), NameStyle::LowerCamelCase);
 
         // Convert return type for property interface.
         let return_type = SwiftReturn {
@@ -83,7 +79,7 @@ pub(super) fn process_properties(
             is_public: prop.is_public,
             operations: ops,
             return_type,
-            comments: vec![],
+            comments: prop.comments,
         });
     }
 
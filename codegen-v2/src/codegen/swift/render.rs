@@ -5,6 +5,7 @@
 // file LICENSE at the root of the source code distribution tree.
 
 use super::{inits::process_deinits, *};
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub struct RenderIntput<'a> {
@@ -18,6 +19,22 @@ pub struct RenderIntput<'a> {
     pub partial_prop_tempalte: &'a str,
 }
 
+impl RenderIntput<'static> {
+    /// Uses the templates which are built into the library.
+    pub fn new(file_info: FileInfo) -> Self {
+        RenderIntput {
+            file_info,
+            struct_template: include_str!("templates/struct.hbs"),
+            enum_template: include_str!("templates/enum.hbs"),
+            extension_template: include_str!("templates/extension.hbs"),
+            proto_template: include_str!("templates/proto.hbs"),
+            partial_init_template: include_str!("templates/partial_init.hbs"),
+            partial_func_tempalte: include_str!("templates/partial_func.hbs"),
+            partial_prop_tempalte: include_str!("templates/partial_prop.hbs"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct GeneratedSwiftTypesStrings {
     pub structs: Vec<(String, String)>,
@@ -46,6 +63,32 @@ pub fn pretty_name(name: String) -> String {
     name.replace("_", "").replace("TW", "").replace("Proto", "")
 }
 
+/// Like `render_to_strings`, but returns each rendered file alongside its path
+/// relative to the output directory. Enums and Protobuf declarations go into
+/// their own subfolders.
+pub fn render_to_files(input: RenderIntput<'_>) -> Result<Vec<(PathBuf, String)>> {
+    let rendered = render_to_strings(input)?;
+
+    let mut outputs = vec![];
+    for (name, rendered) in rendered.structs {
+        outputs.push((PathBuf::from(format!("{name}.swift")), rendered));
+    }
+    for (name, rendered) in rendered.enums {
+        outputs.push((Path::new("Enums").join(format!("{name}.swift")), rendered));
+    }
+    for (name, rendered) in rendered.extensions {
+        outputs.push((PathBuf::from(format!("{name}+Extension.swift")), rendered));
+    }
+    for (name, rendered) in rendered.protos {
+        outputs.push((
+            Path::new("Protobuf").join(format!("{name}+Proto.swift")),
+            rendered,
+        ));
+    }
+
+    Ok(outputs)
+}
+
 pub fn render_to_strings<'a>(input: RenderIntput<'a>) -> Result<GeneratedSwiftTypesStrings> {
     // The current year for the copyright header in the generated bindings.
     let current_year = crate::current_year();
@@ -173,11 +216,12 @@ pub fn generate_swift_types(mut info: FileInfo) -> Result<GeneratedSwiftTypes> {
             name: pretty_struct_name,
             is_class: strct.is_class,
             is_public: strct.is_public,
+            deprecated: SwiftDeprecation::new(strct.is_deprecated, strct.deprecation_note),
             init_instance: strct.is_class,
             superclasses,
             eq_operator,
-            inits: inits,
-            deinits: deinits,
+            inits,
+            deinits,
             methods,
             properties,
         });
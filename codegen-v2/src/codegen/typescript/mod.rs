@@ -0,0 +1,355 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::{explicit_params, take_associated, take_functions, transform_name, NameStyle};
+use crate::manifest::{
+    DeinitInfo, EnumInfo, FileInfo, FunctionInfo, InitInfo, Manifest, ParamInfo, PropertyInfo,
+    StructInfo, TypeInfo, TypeVariant,
+};
+use crate::Result;
+use handlebars::Handlebars;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+
+const FILE_TEMPLATE: &str = include_str!("templates/file.hbs");
+
+/// Represents an exported TypeScript class of the WASM module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TsClass {
+    name: String,
+    static_methods: Vec<TsFunction>,
+    methods: Vec<TsFunction>,
+    has_delete: bool,
+}
+
+/// Represents an exported TypeScript enum.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TsEnum {
+    name: String,
+    variants: Vec<TsEnumVariant>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TsEnumVariant {
+    name: String,
+    value: String,
+}
+
+/// Represents a TypeScript method signature.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TsFunction {
+    name: String,
+    params: Vec<TsParam>,
+    #[serde(rename = "return")]
+    return_type: TsType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TsParam {
+    name: String,
+    #[serde(rename = "type")]
+    param_type: TsType,
+}
+
+// Wrapper around a valid TypeScript type (built in or custom), including the
+// `| null` of nullable types. Meant to be used as
+// `<TsType as From<TypeInfo>>::from(...)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TsType(String);
+
+impl Display for TsType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Convert the `TypeVariant` into the appropriate TypeScript type.
+impl From<TypeVariant> for TsType {
+    fn from(value: TypeVariant) -> Self {
+        let res = match value {
+            TypeVariant::Void => "void".to_string(),
+            TypeVariant::Bool => "boolean".to_string(),
+            TypeVariant::Char | TypeVariant::String | TypeVariant::CString => "string".to_string(),
+            TypeVariant::ShortInt
+            | TypeVariant::Int
+            | TypeVariant::UnsignedInt
+            | TypeVariant::Float
+            | TypeVariant::Double
+            | TypeVariant::SizeT
+            | TypeVariant::IntPtr
+            | TypeVariant::Int8T
+            | TypeVariant::Int16T
+            | TypeVariant::Int32T
+            | TypeVariant::UInt8T
+            | TypeVariant::UInt16T
+            | TypeVariant::UInt32T => "number".to_string(),
+            TypeVariant::LongInt
+            | TypeVariant::UnsignedLongInt
+            | TypeVariant::Int64T
+            | TypeVariant::UInt64T => "bigint".to_string(),
+            TypeVariant::Data => "Uint8Array".to_string(),
+            TypeVariant::Array { element, .. } => format!("{}[]", TsType::from(*element)),
+            // E.g. `((arg0: number) => void)`, parenthesized so it can be
+            // combined with `| null`.
+            TypeVariant::FunctionPointer { ret, params } => {
+                let params = params
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, ty)| format!("arg{idx}: {}", TsType::from(ty)))
+                    .collect::<Vec<_>>();
+
+                format!(
+                    "(({}) => {})",
+                    params.join(", "),
+                    TsType::from(ret.as_ref())
+                )
+            }
+            TypeVariant::Unknown(raw) => raw,
+            TypeVariant::Struct(n) | TypeVariant::Enum(n) => {
+                // We strip the "TW" prefix for TypeScript representations of
+                // structs/enums.
+                n.strip_prefix("TW").map(|n| n.to_string()).unwrap_or(n)
+            }
+        };
+
+        TsType(res)
+    }
+}
+
+impl From<&TypeInfo> for TsType {
+    fn from(ty: &TypeInfo) -> Self {
+        let ts = TsType::from(ty.variant.clone());
+
+        if ty.is_nullable {
+            TsType(format!("{ts} | null"))
+        } else {
+            ts
+        }
+    }
+}
+
+impl From<&ParamInfo> for TsParam {
+    fn from(param: &ParamInfo) -> Self {
+        TsParam {
+            name: param.name.clone(),
+            param_type: TsType::from(&param.ty),
+        }
+    }
+}
+
+/// Represents the types imported from the declarations of another file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TsImport {
+    names: Vec<String>,
+    file: String,
+}
+
+/// Generates the TypeScript declarations (`.d.ts`) of the WASM bindings for
+/// all structs and enums of the given file. Types declared by other files of
+/// the manifest are imported from their declarations.
+pub fn generate_dts(file: &FileInfo, manifest: &Manifest) -> Result<String> {
+    let mut engine = Handlebars::new();
+    // Unmatched variables should result in an error.
+    engine.set_strict_mode(true);
+    // Types such as `string | null` must not be HTML-escaped.
+    engine.register_escape_fn(handlebars::no_escape);
+
+    engine.register_template_string("file", FILE_TEMPLATE)?;
+
+    let mut inits = file.inits.clone();
+    let mut deinits = file.deinits.clone();
+    let mut functions = file.functions.clone();
+    let mut properties = file.properties.clone();
+
+    let mut classes = vec![];
+    for strct in &file.structs {
+        let class = process_struct(
+            strct,
+            &mut inits,
+            &mut deinits,
+            &mut functions,
+            &mut properties,
+        );
+
+        // Avoid rendering empty classes.
+        if class.static_methods.is_empty() && class.methods.is_empty() {
+            continue;
+        }
+
+        classes.push(class);
+    }
+
+    let mut enums = vec![];
+    for enm in &file.enums {
+        let (tsenum, class) = process_enum(enm, &mut functions, &mut properties);
+        enums.push(tsenum);
+
+        // Associated methods of enums are exported via a separate class.
+        if !class.static_methods.is_empty() {
+            classes.push(class);
+        }
+    }
+
+    let out = engine.render(
+        "file",
+        &serde_json::json!({
+            "current_year": crate::current_year(),
+            "imports": process_imports(file, manifest),
+            "enums": enums,
+            "classes": classes,
+        }),
+    )?;
+
+    Ok(out)
+}
+
+// Groups the types declared by other files by the declaring file, e.g.
+// `import type { CoinType } from "./TWCoinType";`.
+fn process_imports(file: &FileInfo, manifest: &Manifest) -> Vec<TsImport> {
+    let mut imports: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+
+    for name in file.types().flat_map(|ty| ty.variant.referenced_names()) {
+        // Types of other files are only referenced by the files using them.
+        let Some(defining) = manifest.defining_file(name) else {
+            continue;
+        };
+        let is_declared = defining
+            .structs
+            .iter()
+            .any(|s| &s.name == name && s.is_public)
+            || defining
+                .enums
+                .iter()
+                .any(|e| &e.name == name && e.is_public);
+        if defining.name == file.name || !is_declared {
+            continue;
+        }
+
+        let ts_name = TsType::from(TypeVariant::Struct(name.clone())).0;
+        imports.entry(&defining.name).or_default().insert(ts_name);
+    }
+
+    imports
+        .into_iter()
+        .map(|(file, names)| TsImport {
+            names: names.into_iter().collect(),
+            file: file.to_string(),
+        })
+        .collect()
+}
+
+fn process_struct(
+    strct: &StructInfo,
+    inits: &mut Vec<InitInfo>,
+    deinits: &mut Vec<DeinitInfo>,
+    functions: &mut Vec<FunctionInfo>,
+    properties: &mut Vec<PropertyInfo>,
+) -> TsClass {
+    let name = TsType::from(TypeVariant::Struct(strct.name.clone())).0;
+
+    // Constructors are exposed as static factory methods.
+    let mut static_methods = take_associated(inits, &strct.name, |init| &init.name)
+        .into_iter()
+        .map(|init| TsFunction {
+            name: transform_name(&init.name, Some(&strct.name), NameStyle::LowerCamelCase),
+            params: init.params.iter().map(TsParam::from).collect(),
+            return_type: TsType(if init.is_nullable {
+                format!("{name} | null")
+            } else {
+                name.clone()
+            }),
+        })
+        .collect::<Vec<_>>();
+
+    let mut methods = vec![];
+    for func in take_functions(functions, &strct.name) {
+        if func.is_static {
+            static_methods.push(process_function(&func, &strct.name));
+        } else {
+            methods.push(process_function(&func, &strct.name));
+        }
+    }
+
+    methods.extend(process_properties(properties, &strct.name));
+
+    TsClass {
+        name,
+        static_methods,
+        methods,
+        has_delete: !take_associated(deinits, &strct.name, |d| &d.name).is_empty(),
+    }
+}
+
+fn process_enum(
+    enm: &EnumInfo,
+    functions: &mut Vec<FunctionInfo>,
+    properties: &mut Vec<PropertyInfo>,
+) -> (TsEnum, TsClass) {
+    let name = TsType::from(TypeVariant::Enum(enm.name.clone())).0;
+
+    let variants = enm
+        .variants
+        .iter()
+        .map(|variant| TsEnumVariant {
+            name: variant.name.clone(),
+            value: variant.value.clone(),
+        })
+        .collect();
+
+    // Associated methods and properties become static methods, which are
+    // passed on the enum value explicitly.
+    let mut methods = take_functions(functions, &enm.name)
+        .into_iter()
+        .map(|func| TsFunction {
+            name: transform_name(&func.name, Some(&enm.name), NameStyle::LowerCamelCase),
+            params: func.params.iter().map(TsParam::from).collect(),
+            return_type: TsType::from(&func.return_type),
+        })
+        .collect::<Vec<_>>();
+
+    for prop in take_associated(properties, &enm.name, |p| &p.name) {
+        methods.push(TsFunction {
+            name: transform_name(&prop.name, Some(&enm.name), NameStyle::LowerCamelCase),
+            params: vec![TsParam {
+                name: "value".to_string(),
+                param_type: TsType(name.clone()),
+            }],
+            return_type: TsType::from(&prop.return_type),
+        });
+    }
+
+    let class = TsClass {
+        name: format!("{name}Ext"),
+        static_methods: methods,
+        methods: vec![],
+        has_delete: false,
+    };
+
+    (TsEnum { name, variants }, class)
+}
+
+fn process_function(func: &FunctionInfo, object_name: &str) -> TsFunction {
+    TsFunction {
+        name: transform_name(&func.name, Some(object_name), NameStyle::LowerCamelCase),
+        params: explicit_params(func, object_name)
+            .iter()
+            .map(TsParam::from)
+            .collect(),
+        return_type: TsType::from(&func.return_type),
+    }
+}
+
+// Properties are exposed as getter methods, following the WASM bindings.
+fn process_properties(properties: &mut Vec<PropertyInfo>, object_name: &str) -> Vec<TsFunction> {
+    take_associated(properties, object_name, |p| &p.name)
+        .into_iter()
+        .map(|prop| TsFunction {
+            name: transform_name(&prop.name, Some(object_name), NameStyle::LowerCamelCase),
+            params: vec![],
+            return_type: TsType::from(&prop.return_type),
+        })
+        .collect()
+}
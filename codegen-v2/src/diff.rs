@@ -0,0 +1,205 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::manifest::{resolve_enum_values, EnumInfo, Manifest, TypeInfo};
+use std::collections::BTreeMap;
+
+/// The API changes between two manifests, e.g. of two wallet-core versions.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ManifestDiff {
+    pub changes: Vec<ManifestChange>,
+}
+
+impl ManifestDiff {
+    /// Whether any of the changes breaks existing users of the API.
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(ManifestChange::is_breaking)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ManifestChange {
+    pub kind: ChangeKind,
+    pub item: ItemKind,
+    // Enum variants are named after their enum, e.g. `TWCurve.ed25519`.
+    pub name: String,
+}
+
+impl ManifestChange {
+    /// Removals and changes are breaking, additions are not.
+    pub fn is_breaking(&self) -> bool {
+        self.kind != ChangeKind::Added
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    // The fields of a struct, the backing type of an enum, the value of an
+    // enum variant or the signature of a function changed.
+    Changed,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    Struct,
+    Enum,
+    EnumVariant,
+    // Includes inits, deinits and properties.
+    Function,
+}
+
+/// Compares the public structs, enums and functions of both manifests. A
+/// function counts as changed if its parameter or return types differ;
+/// parameter names are not part of the signature.
+pub fn diff_manifests(old: &Manifest, new: &Manifest) -> ManifestDiff {
+    let mut changes = vec![];
+
+    let (old_structs, new_structs) = (structs(old), structs(new));
+    diff_items(&old_structs, &new_structs, ItemKind::Struct, &mut changes);
+
+    let (old_enums, new_enums) = (enums(old), enums(new));
+    for (name, old_enum) in &old_enums {
+        match new_enums.get(name) {
+            Some(new_enum) => {
+                if new_enum.value_type != old_enum.value_type {
+                    changes.push(change(ChangeKind::Changed, ItemKind::Enum, name));
+                }
+                diff_variants(old_enum, new_enum, &mut changes);
+            }
+            None => changes.push(change(ChangeKind::Removed, ItemKind::Enum, name)),
+        }
+    }
+    for name in new_enums
+        .keys()
+        .filter(|name| !old_enums.contains_key(*name))
+    {
+        changes.push(change(ChangeKind::Added, ItemKind::Enum, name));
+    }
+
+    let (old_functions, new_functions) = (functions(old), functions(new));
+    diff_items(
+        &old_functions,
+        &new_functions,
+        ItemKind::Function,
+        &mut changes,
+    );
+
+    ManifestDiff { changes }
+}
+
+// Reports added and removed items, and items whose value differs.
+fn diff_items<K: AsRef<str> + Ord, T: PartialEq>(
+    old: &BTreeMap<K, T>,
+    new: &BTreeMap<K, T>,
+    item: ItemKind,
+    changes: &mut Vec<ManifestChange>,
+) {
+    for (name, old_value) in old {
+        match new.get(name) {
+            Some(new_value) if new_value != old_value => {
+                changes.push(change(ChangeKind::Changed, item, name.as_ref()))
+            }
+            Some(_) => {}
+            None => changes.push(change(ChangeKind::Removed, item, name.as_ref())),
+        }
+    }
+
+    for name in new.keys().filter(|name| !old.contains_key(*name)) {
+        changes.push(change(ChangeKind::Added, item, name.as_ref()));
+    }
+}
+
+// Variants are compared by name, so reordering them is not a change. Values
+// are compared by what they evaluate to, e.g. `0x00` is the same as `0`.
+fn diff_variants(old: &EnumInfo, new: &EnumInfo, changes: &mut Vec<ManifestChange>) {
+    let variants = |enm: &EnumInfo| {
+        enm.variants
+            .iter()
+            .zip(resolve_enum_values(enm))
+            .map(|(v, value)| {
+                let value = value.map_or_else(|| v.value.clone(), |value| value.to_string());
+                (format!("{}.{}", enm.name, v.name), value)
+            })
+            .collect::<BTreeMap<_, _>>()
+    };
+
+    diff_items(
+        &variants(old),
+        &variants(new),
+        ItemKind::EnumVariant,
+        changes,
+    );
+}
+
+fn change(kind: ChangeKind, item: ItemKind, name: &str) -> ManifestChange {
+    ManifestChange {
+        kind,
+        item,
+        name: name.to_string(),
+    }
+}
+
+// The name, type and bit width of every field. Comments are not part of the
+// layout.
+type Fields<'a> = Vec<(&'a str, &'a TypeInfo, Option<u8>)>;
+
+// Non-public structs and enums are references to types of other files.
+fn structs(manifest: &Manifest) -> BTreeMap<&str, Fields<'_>> {
+    manifest
+        .files
+        .iter()
+        .flat_map(|file| &file.structs)
+        .filter(|strct| strct.is_public)
+        .map(|strct| {
+            let fields = strct
+                .fields
+                .iter()
+                .map(|field| (field.name.as_str(), &field.ty, field.bit_width))
+                .collect();
+            (strct.name.as_str(), fields)
+        })
+        .collect()
+}
+
+fn enums(manifest: &Manifest) -> BTreeMap<&str, &EnumInfo> {
+    manifest
+        .files
+        .iter()
+        .flat_map(|file| &file.enums)
+        .filter(|enm| enm.is_public)
+        .map(|enm| (enm.name.as_str(), enm))
+        .collect()
+}
+
+// The parameter types and the return type of every public function.
+type Signature<'a> = (Vec<&'a TypeInfo>, Option<&'a TypeInfo>);
+
+fn functions(manifest: &Manifest) -> BTreeMap<&str, Signature<'_>> {
+    let mut functions = BTreeMap::new();
+
+    for file in &manifest.files {
+        for init in file.inits.iter().filter(|init| init.is_public) {
+            let params = init.params.iter().map(|p| &p.ty).collect();
+            functions.insert(init.name.as_str(), (params, None));
+        }
+        for deinit in &file.deinits {
+            functions.insert(deinit.name.as_str(), (vec![], None));
+        }
+        for func in file.functions.iter().filter(|func| func.is_public) {
+            let params = func.params.iter().map(|p| &p.ty).collect();
+            functions.insert(func.name.as_str(), (params, Some(&func.return_type)));
+        }
+        for prop in file.properties.iter().filter(|prop| prop.is_public) {
+            functions.insert(prop.name.as_str(), (vec![], Some(&prop.return_type)));
+        }
+    }
+
+    functions
+}
@@ -8,13 +8,18 @@
 extern crate serde;
 
 use handlebars::{RenderError, TemplateError};
+use serde_json::Error as JsonError;
 use serde_yaml::Error as YamlError;
 use std::io::Error as IoError;
 
+pub mod builder;
 pub mod codegen;
+pub mod diff;
 pub mod manifest;
+pub mod schema;
 #[cfg(test)]
 mod tests;
+pub mod visit;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -22,12 +27,53 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     IoError(IoError),
     YamlError(YamlError),
-    RenderError(RenderError),
-    TemplateError(TemplateError),
+    JsonError(JsonError),
+    // Boxed, since the handlebars errors are comparatively large.
+    RenderError(Box<RenderError>),
+    TemplateError(Box<TemplateError>),
     BadFormat(String),
+    BadImport(String),
+    // Types which could not be classified, in strict mode.
+    BadType(String),
+    // The target language cannot be generated by `codegen::generate`.
+    UnsupportedTarget(String),
     InvalidCommand,
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IoError(err) => write!(f, "I/O error: {err}"),
+            Error::YamlError(err) => write!(f, "YAML error: {err}"),
+            Error::JsonError(err) => write!(f, "JSON error: {err}"),
+            Error::RenderError(err) => write!(f, "failed to render template: {err}"),
+            Error::TemplateError(err) => write!(f, "invalid template: {err}"),
+            Error::BadFormat(msg) => write!(f, "bad format: {msg}"),
+            Error::BadImport(msg) => write!(f, "bad import: {msg}"),
+            Error::BadType(msg) => write!(f, "bad type: {msg}"),
+            Error::UnsupportedTarget(msg) => write!(f, "unsupported target: {msg}"),
+            Error::InvalidCommand => write!(f, "invalid command"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::IoError(err) => Some(err),
+            Error::YamlError(err) => Some(err),
+            Error::JsonError(err) => Some(err),
+            Error::RenderError(err) => Some(err.as_ref()),
+            Error::TemplateError(err) => Some(err.as_ref()),
+            Error::BadFormat(_)
+            | Error::BadImport(_)
+            | Error::BadType(_)
+            | Error::UnsupportedTarget(_)
+            | Error::InvalidCommand => None,
+        }
+    }
+}
+
 impl From<IoError> for Error {
     fn from(err: IoError) -> Self {
         Error::IoError(err)
@@ -40,15 +86,21 @@ impl From<YamlError> for Error {
     }
 }
 
+impl From<JsonError> for Error {
+    fn from(err: JsonError) -> Self {
+        Error::JsonError(err)
+    }
+}
+
 impl From<RenderError> for Error {
     fn from(err: RenderError) -> Self {
-        Error::RenderError(err)
+        Error::RenderError(Box::new(err))
     }
 }
 
 impl From<TemplateError> for Error {
     fn from(err: TemplateError) -> Self {
-        Error::TemplateError(err)
+        Error::TemplateError(Box::new(err))
     }
 }
 
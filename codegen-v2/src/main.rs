@@ -4,89 +4,365 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
-use libparser::codegen::swift::RenderIntput;
-use libparser::manifest::parse_dir;
+use clap::{Parser, Subcommand, ValueEnum};
+use libparser::codegen::swift::{render_to_files, RenderIntput};
+use libparser::codegen::{generate_file, generate_files, TargetLanguage};
+use libparser::manifest::{
+    parse_dir, parse_dir_incremental, parse_dir_with_options, parse_file, write_manifests,
+    write_manifests_to, FileInfo, Manifest, ManifestCache, ManifestFormat, OutputSink,
+    ParseOptions, WriteOptions,
+};
 use libparser::{Error, Result};
 use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
 
+/// The default output directory for generated bindings.
+const DEFAULT_OUT_DIR: &str = "bindings/";
+/// The default directory of the YAML manifest files.
+const DEFAULT_MANIFEST_DIR: &str = "manifest/";
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "parser",
+    about = "Generates bindings from the wallet-core manifest"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Generates the Swift bindings.
+    Swift {
+        /// The output directory of the bindings.
+        #[arg(default_value = DEFAULT_OUT_DIR)]
+        out_dir: PathBuf,
+    },
+    /// Generates the bindings of every manifest file for the given language.
+    Generate {
+        #[arg(long, value_enum)]
+        lang: Lang,
+        /// The directory of the YAML manifest files.
+        #[arg(long, default_value = DEFAULT_MANIFEST_DIR)]
+        input: PathBuf,
+        /// The output directory of the bindings.
+        #[arg(default_value = DEFAULT_OUT_DIR)]
+        out_dir: PathBuf,
+        /// The manifest cache, which is reused for unchanged files and updated
+        /// afterwards. Created if it does not exist.
+        #[arg(long)]
+        cache: Option<PathBuf>,
+    },
+    /// Generates the bindings of a single manifest file for the given
+    /// language, e.g. while editing it.
+    GenFile {
+        /// The YAML manifest file.
+        manifest: PathBuf,
+        #[arg(long, value_enum)]
+        lang: Lang,
+        /// The output directory of the bindings. Prints the bindings to stdout
+        /// if not given.
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+        /// The manifest cache written by `generate --cache`, for checking that
+        /// the types of other files are declared.
+        #[arg(long)]
+        cache: Option<PathBuf>,
+    },
+    /// Parses the manifest files and writes them in the given format.
+    Manifest {
+        /// The directory of the YAML manifest files.
+        #[arg(long, default_value = DEFAULT_MANIFEST_DIR)]
+        input: PathBuf,
+        /// The output directory of the written manifest files, or `-` to
+        /// write them to stdout.
+        #[arg(long)]
+        output: PathBuf,
+        #[arg(long, value_enum, default_value_t = Format::Json)]
+        format: Format,
+        /// Writes JSON without indentation.
+        #[arg(long)]
+        compact: bool,
+        /// Only includes the files matching the glob pattern, e.g.
+        /// `TWMnemonic*`. Can be given multiple times.
+        #[arg(long)]
+        include: Vec<String>,
+        /// Fails if any type could not be classified, except for the allowed
+        /// ones.
+        #[arg(long)]
+        strict: bool,
+        /// Allows the unclassified type in strict mode. Can be given multiple
+        /// times.
+        #[arg(long)]
+        allow_unknown: Vec<String>,
+        /// Only prints the files which would be written, with their sizes.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Yaml,
+}
+
+impl From<Format> for ManifestFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Json => ManifestFormat::Json,
+            Format::Yaml => ManifestFormat::Yaml,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Lang {
+    Csharp,
+    Go,
+    Kotlin,
+    Objc,
+    Protobuf,
+    Python,
+    Rust,
+    Swift,
+    Typescript,
+}
+
+impl From<Lang> for TargetLanguage {
+    fn from(lang: Lang) -> Self {
+        match lang {
+            Lang::Csharp => TargetLanguage::CSharp,
+            Lang::Go => TargetLanguage::Go,
+            Lang::Kotlin => TargetLanguage::Kotlin,
+            Lang::Objc => TargetLanguage::ObjC,
+            Lang::Protobuf => TargetLanguage::Protobuf,
+            Lang::Python => TargetLanguage::Python,
+            Lang::Rust => TargetLanguage::Rust,
+            Lang::Swift => TargetLanguage::Swift,
+            Lang::Typescript => TargetLanguage::TypeScript,
+        }
+    }
+}
+
+// Any error results in a non-zero exit code.
 fn main() -> Result<()> {
-    let args: Vec<String> = std::env::args().collect();
+    match Cli::parse().command {
+        Command::Swift { out_dir } => generate_swift_bindings(&out_dir),
+        Command::Generate {
+            lang,
+            input,
+            out_dir,
+            cache,
+        } => generate_bindings(&input, &out_dir, cache.as_deref(), lang.into()),
+        Command::GenFile {
+            manifest,
+            lang,
+            out_dir,
+            cache,
+        } => generate_file_bindings(&manifest, out_dir.as_deref(), cache.as_deref(), lang.into()),
+        Command::Manifest {
+            input,
+            output,
+            format,
+            compact,
+            include,
+            strict,
+            allow_unknown,
+            dry_run,
+        } => {
+            let options = ParseOptions {
+                include_filter: Some(include),
+                strict,
+                allowed_unknown_types: allow_unknown,
+            };
+            let write_options = WriteOptions {
+                format: format.into(),
+                pretty: !compact,
+                dry_run,
+            };
+            write_manifest_files(&input, &output, &options, &write_options)
+        }
+    }
+}
+
+fn write_manifest_files(
+    input: &Path,
+    output: &Path,
+    options: &ParseOptions,
+    write_options: &WriteOptions,
+) -> Result<()> {
+    let file_infos = parse_dir_with_options(input, options)?;
 
-    if args.len() < 2 {
-        panic!("Invalid command");
+    if output == Path::new("-") {
+        write_manifests_to(&file_infos, &OutputSink::Stdout, write_options)?;
+        return Ok(());
     }
 
-    match args[1].as_str() {
-        "swift" => generate_swift_bindings(),
-        _ => Err(Error::InvalidCommand),
+    let written = write_manifests(&file_infos, output, write_options)?;
+
+    if write_options.dry_run {
+        for (path, len) in &written {
+            println!("{} ({len} bytes)", path.display());
+        }
+        println!("Would write {} manifest files.", written.len());
+        return Ok(());
     }
+
+    println!(
+        "Wrote {} manifest files to directory '{}'!",
+        written.len(),
+        output.display()
+    );
+    Ok(())
 }
 
-fn generate_swift_bindings() -> Result<()> {
-    // NOTE: The paths will be configurable, eventually.
-    const OUT_DIR: &str = "bindings/";
-    const IN_DIR: &str = "src/codegen/swift/templates";
+fn generate_bindings(
+    input: &Path,
+    out_dir: &Path,
+    cache: Option<&Path>,
+    target: TargetLanguage,
+) -> Result<()> {
+    let file_infos = match cache {
+        Some(cache_path) => {
+            let mut cache = ManifestCache::load(cache_path)?;
+            let file_infos = parse_dir_incremental(input, &mut cache)?;
+            cache.save(cache_path)?;
+            file_infos
+        }
+        None => parse_dir(input)?,
+    };
+
+    for (path, rendered) in generate_files(&file_infos, target)? {
+        write_output(&out_dir.join(path), &rendered)?;
+    }
+
+    println!(
+        "Created {} {target:?} bindings in directory '{}'!",
+        file_infos.len(),
+        out_dir.display()
+    );
+    Ok(())
+}
 
-    std::fs::create_dir_all(OUT_DIR)?;
+fn generate_swift_bindings(out_dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(out_dir)?;
 
-    let struct_t = read_to_string(&format!("{IN_DIR}/struct.hbs"))?;
-    let enum_t = read_to_string(&format!("{IN_DIR}/enum.hbs"))?;
-    let ext_t = read_to_string(&format!("{IN_DIR}/extension.hbs"))?;
-    let proto_t = read_to_string(&format!("{IN_DIR}/proto.hbs"))?;
-    let part_init_t = read_to_string(&format!("{IN_DIR}/partial_init.hbs"))?;
-    let part_func_t = read_to_string(&format!("{IN_DIR}/partial_func.hbs"))?;
-    let part_prop_t = read_to_string(&format!("{IN_DIR}/partial_prop.hbs"))?;
+    let templates = SwiftTemplates::load()?;
 
     // Read the manifest dir, generate bindings for each entry.
     let file_infos = parse_dir("manifest/")?;
 
     for file_info in file_infos {
-        let input = RenderIntput {
-            file_info,
-            struct_template: &struct_t,
-            enum_template: &enum_t,
-            extension_template: &ext_t,
-            proto_template: &proto_t,
-            partial_init_template: &part_init_t,
-            partial_func_tempalte: &part_func_t,
-            partial_prop_tempalte: &part_prop_t,
-        };
-
-        let rendered = libparser::codegen::swift::render_to_strings(input)?;
-
-        // Enum declarations go into their own subfolder.
-        if !rendered.enums.is_empty() {
-            std::fs::create_dir_all(format!("{OUT_DIR}/Enums"))?;
+        for (path, rendered) in render_to_files(templates.input(file_info))? {
+            write_output(&out_dir.join(path), &rendered)?;
         }
+    }
 
-        // Protobuf declarations go into their own subfolder.
-        if !rendered.protos.is_empty() {
-            std::fs::create_dir_all(format!("{OUT_DIR}/Protobuf"))?;
-        }
+    println!("Created bindings in directory '{}'!", out_dir.display());
+    Ok(())
+}
 
-        for (name, rendered) in rendered.structs {
-            let file_path = format!("{OUT_DIR}/{name}.swift");
-            std::fs::write(&file_path, rendered.as_bytes())?;
-        }
+fn generate_file_bindings(
+    manifest: &Path,
+    out_dir: Option<&Path>,
+    cache: Option<&Path>,
+    target: TargetLanguage,
+) -> Result<()> {
+    let (_, file_info) = parse_file(manifest)?;
 
-        for (name, rendered) in rendered.enums {
-            let file_path = format!("{OUT_DIR}/Enums/{name}.swift");
-            std::fs::write(&file_path, rendered.as_bytes())?;
-        }
+    // Types of other files must be declared by one of the previously parsed
+    // files.
+    let mut files = vec![file_info.clone()];
+    if let Some(cache) = cache {
+        let cache = ManifestCache::load(cache)?;
+        let declared = cache
+            .entries
+            .values()
+            .flat_map(|(_, info)| {
+                let structs = info.structs.iter().filter(|s| s.is_public);
+                let enums = info.enums.iter().filter(|e| e.is_public);
+                structs
+                    .map(|s| s.name.as_str())
+                    .chain(enums.map(|e| e.name.as_str()))
+            })
+            .collect::<Vec<_>>();
 
-        // Enum extensions.
-        for (name, rendered) in rendered.extensions {
-            let file_path = format!("{OUT_DIR}/{name}+Extension.swift");
-            std::fs::write(&file_path, rendered.as_bytes())?;
+        let unresolved = file_info.unresolved_types(&declared);
+        if !unresolved.is_empty() {
+            return Err(Error::BadType(format!(
+                "{} references undeclared types: {}",
+                file_info.name,
+                unresolved.join(", ")
+            )));
         }
 
-        // Protobuf messages.
-        for (name, rendered) in rendered.protos {
-            let file_path = format!("{OUT_DIR}/Protobuf/{name}+Proto.swift");
-            std::fs::write(&file_path, rendered.as_bytes())?;
+        // The cached version of this file is superseded.
+        let cached = cache.entries.into_values().map(|(_, info)| info);
+        files.extend(cached.filter(|info| info.name != file_info.name));
+    }
+
+    let manifest = Manifest::from_files(files);
+    for (path, rendered) in generate_file(&file_info, &manifest, target)? {
+        match out_dir {
+            Some(out_dir) => write_output(&out_dir.join(path), &rendered)?,
+            None => print!("{rendered}"),
         }
     }
 
-    println!("Created bindings in directory 'bindings/'!");
     Ok(())
 }
+
+// Writes the file, creating its directory if necessary.
+fn write_output(path: &Path, content: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    std::fs::write(path, content.as_bytes())?;
+    Ok(())
+}
+
+/// The Handlebars templates of the Swift bindings.
+struct SwiftTemplates {
+    struct_t: String,
+    enum_t: String,
+    ext_t: String,
+    proto_t: String,
+    part_init_t: String,
+    part_func_t: String,
+    part_prop_t: String,
+}
+
+impl SwiftTemplates {
+    fn load() -> Result<Self> {
+        // NOTE: The template path will be configurable, eventually.
+        const IN_DIR: &str = "src/codegen/swift/templates";
+
+        Ok(SwiftTemplates {
+            struct_t: read_to_string(format!("{IN_DIR}/struct.hbs"))?,
+            enum_t: read_to_string(format!("{IN_DIR}/enum.hbs"))?,
+            ext_t: read_to_string(format!("{IN_DIR}/extension.hbs"))?,
+            proto_t: read_to_string(format!("{IN_DIR}/proto.hbs"))?,
+            part_init_t: read_to_string(format!("{IN_DIR}/partial_init.hbs"))?,
+            part_func_t: read_to_string(format!("{IN_DIR}/partial_func.hbs"))?,
+            part_prop_t: read_to_string(format!("{IN_DIR}/partial_prop.hbs"))?,
+        })
+    }
+}
+
+impl SwiftTemplates {
+    fn input(&self, file_info: FileInfo) -> RenderIntput<'_> {
+        RenderIntput {
+            file_info,
+            struct_template: &self.struct_t,
+            enum_template: &self.enum_t,
+            extension_template: &self.ext_t,
+            proto_template: &self.proto_t,
+            partial_init_template: &self.part_init_t,
+            partial_func_tempalte: &self.part_func_t,
+            partial_prop_tempalte: &self.part_prop_t,
+        }
+    }
+}
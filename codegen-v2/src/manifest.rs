@@ -4,80 +4,1478 @@
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
 
-use super::Result;
+use super::{Error, Result};
+use heck::ToUpperCamelCase;
+use rayon::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::iter::Peekable;
+use std::path::{Component, Path, PathBuf};
+
+/// Opaque C types which represent a byte buffer and are mapped to
+/// `TypeVariant::Data`.
+pub const BYTE_TYPES: &[&str] = &["TWData"];
+
+/// All manifest files of a directory, combined with an index of where each
+/// struct and enum is defined.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub files: Vec<FileInfo>,
+    // Maps every struct/enum name to the manifest file defining it. Ordered,
+    // so the serialized manifest is deterministic.
+    pub type_index: BTreeMap<String, PathBuf>,
+    // Maps every file name to the path of its manifest file.
+    pub file_paths: BTreeMap<String, PathBuf>,
+}
+
+impl Manifest {
+    /// Returns the file which defines the given struct or enum, e.g.
+    /// `TWPublicKey`, while generating bindings for any other file.
+    pub fn defining_file(&self, type_name: &str) -> Option<&FileInfo> {
+        let path = self.type_index.get(type_name)?;
+        let name = path.file_stem()?.to_str()?;
+
+        self.files.iter().find(|file| file.name == name)
+    }
+}
+
+/// Returns the struct and enum names which are publicly declared more than
+/// once, along with the manifest paths of the declaring files. A struct and
+/// an enum of the same name are duplicates, too.
+pub fn find_duplicate_types(manifest: &Manifest) -> Vec<(String, Vec<PathBuf>)> {
+    let mut declarations: BTreeMap<&String, Vec<PathBuf>> = BTreeMap::new();
+
+    for file in &manifest.files {
+        let path = manifest
+            .file_paths
+            .get(&file.name)
+            .cloned()
+            .unwrap_or_else(|| PathBuf::from(&file.name));
+
+        // Non-public declarations are references to types of other files.
+        let names = file
+            .structs
+            .iter()
+            .filter(|s| s.is_public)
+            .map(|s| &s.name)
+            .chain(file.enums.iter().filter(|e| e.is_public).map(|e| &e.name));
+
+        for name in names {
+            declarations.entry(name).or_default().push(path.clone());
+        }
+    }
+
+    declarations
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|(name, paths)| (name.clone(), paths))
+        .collect()
+}
+
+/// How a struct is passed across the C interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructKind {
+    /// Has no public fields and is only used behind pointers, i.e. a handle.
+    Opaque,
+    /// Has public fields or is passed by value, i.e. needs to be marshalled.
+    Value,
+}
+
+/// Classifies every public struct of the manifest as either opaque or a
+/// value type, based on its fields and on how the files reference it.
+pub fn classify_structs(manifest: &Manifest) -> HashMap<String, StructKind> {
+    let by_value = manifest
+        .files
+        .iter()
+        .flat_map(|file| file.types())
+        .filter(|ty| !ty.is_pointer())
+        .filter_map(|ty| match &ty.variant {
+            TypeVariant::Struct(name) => Some(name),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    manifest
+        .files
+        .iter()
+        .flat_map(|file| &file.structs)
+        .filter(|strct| strct.is_public)
+        .map(|strct| {
+            let kind = if strct.fields.is_empty() && !by_value.contains(&&strct.name) {
+                StructKind::Opaque
+            } else {
+                StructKind::Value
+            };
+
+            (strct.name.clone(), kind)
+        })
+        .collect()
+}
+
+/// Returns the names of all C symbols exported by the manifest files, sorted
+/// and deduplicated, e.g. for the version script of the shared library. Those
+/// are the public functions, properties and initializers, plus the
+/// deinitializers.
+pub fn exported_symbols(manifest: &Manifest) -> Vec<String> {
+    let symbols = manifest
+        .files
+        .iter()
+        .flat_map(|file| {
+            file.functions
+                .iter()
+                .filter(|func| func.is_public)
+                .map(|func| &func.name)
+                .chain(
+                    file.properties
+                        .iter()
+                        .filter(|prop| prop.is_public)
+                        .map(|prop| &prop.name),
+                )
+                .chain(
+                    file.inits
+                        .iter()
+                        .filter(|init| init.is_public)
+                        .map(|init| &init.name),
+                )
+                .chain(file.deinits.iter().map(|deinit| &deinit.name))
+        })
+        .cloned()
+        .collect::<BTreeSet<_>>();
+
+    symbols.into_iter().collect()
+}
+
+/// Returns the public initializers, functions and properties without
+/// documentation comments, qualified by their file, e.g.
+/// `TWMnemonic::TWMnemonicIsValid`, so doc coverage can be enforced.
+pub fn undocumented_methods(manifest: &Manifest) -> Vec<String> {
+    manifest
+        .files
+        .iter()
+        .flat_map(|file| {
+            let inits = file
+                .inits
+                .iter()
+                .filter(|init| init.is_public && init.comments.is_empty())
+                .map(|init| &init.name);
+            let functions = file
+                .functions
+                .iter()
+                .filter(|func| func.is_public && func.comments.is_empty())
+                .map(|func| &func.name);
+            let properties = file
+                .properties
+                .iter()
+                .filter(|prop| prop.is_public && prop.comments.is_empty())
+                .map(|prop| &prop.name);
+
+            inits
+                .chain(functions)
+                .chain(properties)
+                .map(move |name| format!("{}::{name}", file.name))
+        })
+        .collect()
+}
+
+/// Returns the name of the base struct of C pseudo-inheritance, i.e. the
+/// struct the first field holds by value, e.g. `Base` for
+/// `struct Derived { struct Base base; int extra; };`.
+pub fn base_struct(s: &StructInfo) -> Option<String> {
+    let first = s.fields.first()?;
+
+    match &first.ty.variant {
+        TypeVariant::Struct(name) if !first.ty.is_pointer() => Some(name.clone()),
+        _ => None,
+    }
+}
+
+/// An inconsistency of a combined `Manifest`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// A struct or enum is referenced, but not defined by any file.
+    UnknownType { name: String, referenced_in: String },
+}
+
+/// Checks that every struct or enum referenced by the manifest files is
+/// defined by one of the files, returning all violations.
+pub fn validate_manifest(manifest: &Manifest) -> std::result::Result<(), Vec<ValidationError>> {
+    let mut errors = vec![];
+
+    for file in &manifest.files {
+        for name in file.types().flat_map(|ty| ty.variant.referenced_names()) {
+            // Typedefs are local to the file.
+            let is_known = manifest.type_index.contains_key(name)
+                || file.typedefs.iter().any(|t| &t.name == name);
+
+            let error = ValidationError::UnknownType {
+                name: name.clone(),
+                referenced_in: file.name.clone(),
+            };
+
+            if !is_known && !errors.contains(&error) {
+                errors.push(error);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// An inconsistency of an enum declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnumError {
+    /// Multiple variants have the same value.
+    DuplicateValue { value: i128, variants: Vec<String> },
+    /// The value does not fit the value type, e.g. `256` for `uint8_t`.
+    OutOfRange { variant: String, value: i128 },
+    /// The value is neither a literal nor a valid constant expression.
+    InvalidValue { variant: String, value: String },
+}
+
+/// Checks that the variant values of the enum are distinct and fit its value
+/// type, returning all violations. Constant expressions are evaluated first.
+/// Since `long` is 32 bits on some platforms, its values must fit 32 bits.
+pub fn validate_enum(enm: &EnumInfo) -> std::result::Result<(), Vec<EnumError>> {
+    let range = match enm.value_type {
+        TypeVariant::Char | TypeVariant::Int8T => Some(i8::MIN as i128..=i8::MAX as i128),
+        TypeVariant::UInt8T => Some(0..=u8::MAX as i128),
+        TypeVariant::ShortInt | TypeVariant::Int16T => Some(i16::MIN as i128..=i16::MAX as i128),
+        TypeVariant::UInt16T => Some(0..=u16::MAX as i128),
+        TypeVariant::Int | TypeVariant::Int32T | TypeVariant::LongInt => {
+            Some(i32::MIN as i128..=i32::MAX as i128)
+        }
+        TypeVariant::UnsignedInt | TypeVariant::UInt32T | TypeVariant::UnsignedLongInt => {
+            Some(0..=u32::MAX as i128)
+        }
+        TypeVariant::Int64T | TypeVariant::IntPtr => Some(i64::MIN as i128..=i64::MAX as i128),
+        TypeVariant::UInt64T | TypeVariant::SizeT => Some(0..=u64::MAX as i128),
+        // Unsupported value types are not checked.
+        _ => None,
+    };
+
+    let mut errors = vec![];
+    let mut by_value: BTreeMap<i128, Vec<String>> = BTreeMap::new();
+
+    for (variant, value) in enm.variants.iter().zip(resolve_enum_values(enm)) {
+        let Some(value) = value else {
+            errors.push(EnumError::InvalidValue {
+                variant: variant.name.clone(),
+                value: variant.value.clone(),
+            });
+            continue;
+        };
+
+        if range.as_ref().is_some_and(|range| !range.contains(&value)) {
+            errors.push(EnumError::OutOfRange {
+                variant: variant.name.clone(),
+                value,
+            });
+        }
+
+        by_value
+            .entry(value)
+            .or_default()
+            .push(variant.name.clone());
+    }
+
+    for (value, variants) in by_value {
+        if variants.len() > 1 {
+            errors.push(EnumError::DuplicateValue { value, variants });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// An instance method whose first parameter is not its owning type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiverMismatch {
+    pub function: String,
+    // The struct or enum the function belongs to, e.g. `TWFoo`.
+    pub expected: String,
+    // `None` if the function has no parameters.
+    pub found: Option<TypeVariant>,
+}
+
+/// Checks that the first parameter of every non-static function is its owning
+/// struct (or enum), e.g. `struct TWFoo *this` for `TWFooSign`. The owner is
+/// the resolved `associated_type`, falling back to the name prefix.
+pub fn check_receiver_consistency(file: &FileInfo) -> Vec<ReceiverMismatch> {
+    let mut mismatches = vec![];
+
+    for func in file.functions.iter().filter(|func| !func.is_static) {
+        let Some(owner) = func
+            .associated_type
+            .as_deref()
+            .or_else(|| file.associated_type(&func.name))
+        else {
+            continue;
+        };
+
+        let found = func.params.first().map(|param| &param.ty.variant);
+        let is_receiver = match found {
+            Some(TypeVariant::Struct(name) | TypeVariant::Enum(name)) => name == owner,
+            _ => false,
+        };
+
+        if !is_receiver {
+            mismatches.push(ReceiverMismatch {
+                function: func.name.clone(),
+                expected: owner.to_string(),
+                found: found.cloned(),
+            });
+        }
+    }
+
+    mismatches
+}
 
 pub fn parse_dir<P: AsRef<Path>>(path: P) -> Result<Vec<FileInfo>> {
+    parse_dir_with_options(path, &ParseOptions::default())
+}
+
+/// Options for parsing a directory of manifest files.
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    // Glob patterns matched against the file names, such as `TWMnemonic*`.
+    // `None` or an empty list includes all files.
+    pub include_filter: Option<Vec<String>>,
+    // Fail with `Error::BadType` if any file contains types which could not be
+    // classified, see `FileInfo::unknown_types`, except for the allowed ones.
+    pub strict: bool,
+    pub allowed_unknown_types: Vec<String>,
+}
+
+impl ParseOptions {
+    fn includes(&self, file_path: &Path) -> bool {
+        let patterns = match &self.include_filter {
+            Some(patterns) if !patterns.is_empty() => patterns,
+            _ => return true,
+        };
+
+        let file_name = file_path
+            .file_name()
+            .map(|n| n.to_string_lossy())
+            .unwrap_or_default();
+
+        patterns
+            .iter()
+            .any(|pattern| glob_match(pattern, &file_name))
+    }
+}
+
+/// Like `parse_dir`, but only parses the files included by the options.
+pub fn parse_dir_with_options<P: AsRef<Path>>(
+    path: P,
+    options: &ParseOptions,
+) -> Result<Vec<FileInfo>> {
+    Ok(parse_dir_entries(path, options)?
+        .into_iter()
+        .map(|(_, info)| info)
+        .collect())
+}
+
+/// Parses all manifest files of the directory into a combined `Manifest`.
+pub fn build_manifest<P: AsRef<Path>>(path: P) -> Result<Manifest> {
+    let entries = parse_dir_entries(path, &ParseOptions::default())?;
+    Ok(Manifest::from_entries(entries))
+}
+
+impl Manifest {
+    /// Combines the already parsed files into a `Manifest`. Files which were
+    /// not parsed from disk are indexed as `<name>.yaml`.
+    pub fn from_files(files: Vec<FileInfo>) -> Self {
+        let entries = files
+            .into_iter()
+            .map(|info| {
+                let path = info
+                    .source_path
+                    .clone()
+                    .unwrap_or_else(|| PathBuf::from(format!("{}.yaml", info.name)));
+                (path, info)
+            })
+            .collect();
+
+        Manifest::from_entries(entries)
+    }
+
+    fn from_entries(entries: Vec<(PathBuf, FileInfo)>) -> Self {
+        let mut type_index = BTreeMap::new();
+        for (file_path, info) in &entries {
+            let names = info
+                .structs
+                .iter()
+                .map(|s| (&s.name, s.is_public))
+                .chain(info.enums.iter().map(|e| (&e.name, e.is_public)));
+
+            // Files might reference types of other files, which are then marked
+            // as non-public. The public declaration takes precedence.
+            for (name, is_public) in names {
+                if is_public {
+                    type_index.insert(name.clone(), file_path.clone());
+                } else {
+                    type_index
+                        .entry(name.clone())
+                        .or_insert_with(|| file_path.clone());
+                }
+            }
+        }
+
+        let file_paths = entries
+            .iter()
+            .map(|(file_path, info)| (info.name.clone(), file_path.clone()))
+            .collect();
+
+        Manifest {
+            file_paths,
+            files: entries
+                .into_iter()
+                .map(|(_, mut info)| {
+                    info.sort_items();
+                    info
+                })
+                .collect(),
+            type_index,
+        }
+    }
+}
+
+/// The serialization format of written manifest files.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ManifestFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+impl ManifestFormat {
+    /// The file extension of the format, e.g. `json`.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ManifestFormat::Json => "json",
+            ManifestFormat::Yaml => "yaml",
+        }
+    }
+}
+
+/// Options for writing manifest files.
+#[derive(Debug, Clone)]
+pub struct WriteOptions {
+    pub format: ManifestFormat,
+    // Whether JSON is indented, for human-readable diffs. YAML is always
+    // indented.
+    pub pretty: bool,
+    // Whether nothing is written, only reporting the files that would be,
+    // e.g. for checking the output in CI.
+    pub dry_run: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        WriteOptions {
+            format: ManifestFormat::default(),
+            pretty: true,
+            dry_run: false,
+        }
+    }
+}
+
+/// Where `write_manifests_to` writes the manifest files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputSink {
+    /// One file per file info, named `{name}.{ext}`.
+    Directory(PathBuf),
+    /// A single document: the file info itself if there is only one, a list
+    /// of all file infos otherwise. E.g. for piping into `jq`.
+    Stdout,
+}
+
+/// Writes each file info to `{out_dir}/{name}.{ext}`, with the extension
+/// depending on the format. Returns the path and the size in bytes of each
+/// written file; in dry-run mode, of each file which would have been written.
+pub fn write_manifests<P: AsRef<Path>>(
+    files: &[FileInfo],
+    out_dir: P,
+    options: &WriteOptions,
+) -> Result<Vec<(PathBuf, usize)>> {
+    write_manifests_to(
+        files,
+        &OutputSink::Directory(out_dir.as_ref().to_path_buf()),
+        options,
+    )
+}
+
+/// Writes the file infos to the given sink. Returns the paths and byte lengths
+/// of the written files, which are none for `OutputSink::Stdout`. In dry-run
+/// mode, nothing is written and the files which would have been are returned.
+pub fn write_manifests_to(
+    files: &[FileInfo],
+    sink: &OutputSink,
+    options: &WriteOptions,
+) -> Result<Vec<(PathBuf, usize)>> {
+    let out_dir = match sink {
+        OutputSink::Directory(out_dir) => out_dir,
+        OutputSink::Stdout if options.dry_run => return Ok(vec![]),
+        OutputSink::Stdout => {
+            let content = match files {
+                [file] => serialize(file, options)?,
+                files => serialize(&files, options)?,
+            };
+
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(content.as_bytes())?;
+            // YAML output already ends with a newline.
+            if !content.ends_with('\n') {
+                stdout.write_all(b"\n")?;
+            }
+
+            return Ok(vec![]);
+        }
+    };
+
+    if !options.dry_run {
+        fs::create_dir_all(out_dir)?;
+    }
+
+    let mut written = vec![];
+    for file in files {
+        let content = serialize(file, options)?;
+
+        let path = out_dir.join(format!("{}.{}", file.name, options.format.extension()));
+        if !options.dry_run {
+            fs::write(&path, &content)?;
+        }
+        written.push((path, content.len()));
+    }
+
+    Ok(written)
+}
+
+fn serialize<T: serde::Serialize>(value: &T, options: &WriteOptions) -> Result<String> {
+    let content = match options.format {
+        ManifestFormat::Json if options.pretty => serde_json::to_string_pretty(value)?,
+        ManifestFormat::Json => serde_json::to_string(value)?,
+        ManifestFormat::Yaml => serde_yaml::to_string(value)?,
+    };
+
+    Ok(content)
+}
+
+/// The version of the `ManifestCache` format, which must be bumped whenever
+/// the serialized `FileInfo` changes.
+pub const CACHE_VERSION: u32 = 1;
+
+/// Parse results of previous runs, keyed by manifest path. Stored as JSON
+/// alongside the build output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestCache {
+    // The `CACHE_VERSION` the cache was written with.
+    pub version: u32,
+    // The content hash and the parsed info of each manifest file.
+    pub entries: BTreeMap<PathBuf, (u64, FileInfo)>,
+}
+
+impl Default for ManifestCache {
+    fn default() -> Self {
+        ManifestCache {
+            version: CACHE_VERSION,
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl ManifestCache {
+    /// Loads the cache from the given path, or returns an empty cache if the
+    /// file does not exist yet. Caches written by another version, or which
+    /// cannot be read as such, are discarded as well, since all files are
+    /// then parsed again anyway.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.into()),
+        };
+
+        match serde_json::from_str::<Self>(&content) {
+            Ok(cache) if cache.version == CACHE_VERSION => Ok(cache),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+/// Like `parse_dir`, but reuses the cached info of files whose content did not
+/// change since the last run. The cache is updated with the new results, and
+/// entries of removed files are dropped.
+pub fn parse_dir_incremental<P: AsRef<Path>>(
+    path: P,
+    cache: &mut ManifestCache,
+) -> Result<Vec<FileInfo>> {
+    let file_paths = manifest_paths(path)?.collect::<Result<Vec<_>>>()?;
+
+    let cached = &cache.entries;
+    let mut entries = file_paths
+        .into_par_iter()
+        .map(|file_path| {
+            let content = fs::read_to_string(&file_path)?;
+            let hash = stable_hash(content.as_bytes());
+
+            let info = match cached.get(&file_path) {
+                Some((cached_hash, info)) if *cached_hash == hash => info.clone(),
+                _ => parse_source(&file_path, &content)?,
+            };
+
+            Ok((file_path, (hash, info)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    entries.sort_by(|(_, (_, a)), (_, (_, b))| a.name.cmp(&b.name));
+    let files = entries.iter().map(|(_, (_, info))| info.clone()).collect();
+    cache.entries = entries.into_iter().collect();
+
+    Ok(files)
+}
+
+fn parse_dir_entries<P: AsRef<Path>>(
+    path: P,
+    options: &ParseOptions,
+) -> Result<Vec<(PathBuf, FileInfo)>> {
+    let mut file_paths = manifest_paths(path)?.collect::<Result<Vec<_>>>()?;
+    file_paths.retain(|file_path| options.includes(file_path));
+
+    // Each file is independent, hence parse them in parallel.
+    let mut file_infos = file_paths
+        .into_par_iter()
+        .map(parse_file)
+        .collect::<Result<Vec<_>>>()?;
+
+    // The directory iteration order is not guaranteed, so keep the output
+    // deterministic.
+    file_infos.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name));
+
+    if options.strict {
+        check_unknown_types(&file_infos, &options.allowed_unknown_types)?;
+    }
+
+    Ok(file_infos)
+}
+
+// Reports all unknown types at once, e.g. `TWFoo: Whatever, Other`, rather
+// than only the first offender.
+fn check_unknown_types(entries: &[(PathBuf, FileInfo)], allowlist: &[String]) -> Result<()> {
+    let offenders = entries
+        .iter()
+        .filter_map(|(_, info)| {
+            let unknown = info
+                .unknown_types()
+                .into_iter()
+                .filter(|raw| !allowlist.contains(raw))
+                .collect::<Vec<_>>();
+
+            (!unknown.is_empty()).then(|| format!("{}: {}", info.name, unknown.join(", ")))
+        })
+        .collect::<Vec<_>>();
+
+    if offenders.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::BadType(format!(
+            "unclassified types in {}",
+            offenders.join("; ")
+        )))
+    }
+}
+
+/// Lazily parses the manifest files of the directory one by one, so only a
+/// single file is held in memory at a time. Unlike `parse_dir`, the files are
+/// yielded in directory iteration order.
+pub fn parse_iter<P: AsRef<Path>>(path: P) -> Result<impl Iterator<Item = Result<FileInfo>>> {
+    Ok(manifest_paths(path)?.map(|file_path| Ok(parse_file(file_path?)?.1)))
+}
+
+/// Parses a single manifest file, returning its path alongside.
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<(PathBuf, FileInfo)> {
+    let path = path.as_ref();
+
+    // Read the file into a string
+    let file_contents = fs::read_to_string(path)?;
+
+    // Deserialize the YAML into a struct
+    let info = parse_source(path, &file_contents)?;
+
+    Ok((path.to_path_buf(), info))
+}
+
+// Like `parse_str`, but records where the info was parsed from.
+fn parse_source(path: &Path, content: &str) -> Result<FileInfo> {
+    let mut info = parse_str(content)?;
+    info.source_path = Some(path.to_path_buf());
+    info.generated_from_hash = Some(format!("{:016x}", stable_hash(content.as_bytes())));
+
+    // Items merged from other files already name the file declaring them.
+    let file_name = path.file_name().map(|n| n.to_string_lossy().into_owned());
+    let defined_in = info
+        .structs
+        .iter_mut()
+        .map(|s| &mut s.defined_in)
+        .chain(info.enums.iter_mut().map(|e| &mut e.defined_in))
+        .chain(info.functions.iter_mut().map(|f| &mut f.defined_in));
+    for defined_in in defined_in.filter(|d| d.is_none()) {
+        *defined_in = file_name.clone();
+    }
+
+    Ok(info)
+}
+
+// FNV-1a, since unlike `DefaultHasher` it is guaranteed to be stable across
+// toolchains.
+fn stable_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// Matches the name against a glob pattern, where `*` matches any sequence of
+// characters and `?` matches a single character.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let (pattern, name) = (pattern.as_bytes(), name.as_bytes());
+    let (mut p, mut n) = (0, 0);
+    // The position of the last `*` and the name position it was tried at.
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` match one more character.
+                Some((star, start)) => {
+                    p = star + 1;
+                    n = start + 1;
+                    backtrack = Some((star, start + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+// Returns the paths of all manifest files in the directory.
+fn manifest_paths<P: AsRef<Path>>(path: P) -> Result<impl Iterator<Item = Result<PathBuf>>> {
     // Get a list of all files in the directory
     let entries = fs::read_dir(path)?;
 
-    let mut file_infos = vec![];
-    for entry in entries {
-        let entry = entry?;
-        let file_path = entry.path();
+    Ok(entries.filter_map(|entry| {
+        let file_path = match entry {
+            Ok(entry) => entry.path(),
+            Err(err) => return Some(Err(err.into())),
+        };
 
         // Skip directories
         if file_path.is_dir() {
             println!("Found unexpected directory: {}", file_path.display());
-            continue;
+            return None;
+        }
+
+        // Only YAML manifests are expected in the directory.
+        if file_path.extension().is_none_or(|ext| ext != "yaml") {
+            return Some(Err(Error::BadFormat(format!(
+                "expected a YAML manifest file, found: {}",
+                file_path.display()
+            ))));
         }
 
-        // Read the file into a string
-        let file_contents = fs::read_to_string(&file_path)?;
+        Some(Ok(file_path))
+    }))
+}
+
+pub fn parse_str(str: &str) -> Result<FileInfo> {
+    let mut info: FileInfo = serde_yaml::from_str(str)?;
+
+    // Associate each function with its owning type, unless the manifest
+    // specifies it explicitly.
+    let associated = info
+        .functions
+        .iter()
+        .map(|func| {
+            func.associated_type
+                .clone()
+                .or_else(|| info.associated_type(&func.name).map(str::to_string))
+        })
+        .collect::<Vec<_>>();
 
-        // Deserialize the JSON into a struct
-        let info = parse_str(&file_contents)?;
-        file_infos.push(info);
+    for (func, associated) in info.functions.iter_mut().zip(associated) {
+        func.associated_type = associated;
     }
 
-    Ok(file_infos)
+    for ty in info.types_mut() {
+        ty.variant.map_byte_types();
+    }
+
+    for init in &mut info.inits {
+        name_params(&mut init.params);
+    }
+    for func in &mut info.functions {
+        name_params(&mut func.params);
+    }
+
+    for enm in &mut info.enums {
+        evaluate_enum_values(enm);
+    }
+
+    for init in &mut info.inits {
+        init.comments = normalize_comments(&init.comments);
+    }
+    for func in &mut info.functions {
+        func.comments = normalize_comments(&func.comments);
+    }
+    for prop in &mut info.properties {
+        prop.comments = normalize_comments(&prop.comments);
+    }
+    for field in info.structs.iter_mut().flat_map(|s| &mut s.fields) {
+        field.comments = normalize_comments(&field.comments);
+    }
+    for variant in info.enums.iter_mut().flat_map(|e| &mut e.variants) {
+        variant.comments = normalize_comments(&variant.comments);
+    }
+
+    Ok(info)
 }
 
-pub fn parse_str(str: &str) -> Result<FileInfo> {
-    serde_yaml::from_str(str).map_err(|err| err.into())
+// Names unnamed parameters by their position, such as `arg1` for the `const
+// char*` of `void TWFoo(int x, const char*)`. Named parameters are kept.
+fn name_params(params: &mut [ParamInfo]) {
+    for (idx, param) in params.iter_mut().enumerate() {
+        if param.name.is_empty() {
+            param.name = format!("arg{idx}");
+        }
+    }
+}
+
+// Replaces constant expressions of enum values, such as `1 << 8` or
+// `FlagA | FlagB`, with their computed value. Plain decimal or hex literals are
+// kept as is, and so are invalid values, for `validate_enum` to report them.
+fn evaluate_enum_values(enm: &mut EnumInfo) {
+    let values = resolve_enum_values(enm);
+
+    for (variant, value) in enm.variants.iter_mut().zip(values) {
+        if let Some(value) = value.filter(|_| parse_int_literal(&variant.value).is_none()) {
+            variant.value = value.to_string();
+        }
+    }
+}
+
+// Evaluates the values of the enum variants, in order, which are `None` if
+// invalid. Earlier variants can be referred to by their manifest or C name,
+// e.g. `flagA` or `TWFlagsFlagA`.
+pub(crate) fn resolve_enum_values(enm: &EnumInfo) -> Vec<Option<i128>> {
+    let mut known: Vec<(String, i128)> = vec![];
+
+    enm.variants
+        .iter()
+        .map(|variant| {
+            let value = evaluate_const_expr(&variant.value, &known)?;
+            known.push((variant.name.clone(), value));
+            known.push((
+                format!("{}{}", enm.name, variant.name.to_upper_camel_case()),
+                value,
+            ));
+            Some(value)
+        })
+        .collect()
+}
+
+// Evaluates an expression of integer literals and known constants, combined
+// with `<<`, `>>`, `|` and unary `-`, e.g. `(1 << 3) | FlagA` or `-1`.
+fn evaluate_const_expr(expr: &str, known: &[(String, i128)]) -> Option<i128> {
+    let mut tokens = vec![];
+    let mut chars = expr.trim().chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' | '\t' => {
+                chars.next();
+            }
+            '|' | '(' | ')' | '-' => {
+                tokens.push(c.to_string());
+                chars.next();
+            }
+            '<' | '>' => {
+                chars.next();
+                if chars.next() != Some(c) {
+                    return None;
+                }
+                tokens.push(format!("{c}{c}"));
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let mut token = String::new();
+                while let Some(&c) = chars
+                    .peek()
+                    .filter(|c| c.is_ascii_alphanumeric() || **c == '_')
+                {
+                    token.push(c);
+                    chars.next();
+                }
+                tokens.push(token);
+            }
+            _ => return None,
+        }
+    }
+
+    let mut tokens = tokens.iter().map(String::as_str).peekable();
+    let value = parse_or_expr(&mut tokens, known)?;
+
+    // Reject trailing tokens.
+    tokens.next().is_none().then_some(value)
+}
+
+fn parse_or_expr<'a, I>(tokens: &mut Peekable<I>, known: &[(String, i128)]) -> Option<i128>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let mut value = parse_shift_expr(tokens, known)?;
+    while tokens.next_if_eq(&"|").is_some() {
+        value |= parse_shift_expr(tokens, known)?;
+    }
+
+    Some(value)
+}
+
+fn parse_shift_expr<'a, I>(tokens: &mut Peekable<I>, known: &[(String, i128)]) -> Option<i128>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let mut value = parse_primary_expr(tokens, known)?;
+    while let Some(op) = tokens.next_if(|t| *t == "<<" || *t == ">>") {
+        let shift = u32::try_from(parse_primary_expr(tokens, known)?).ok()?;
+        value = if op == "<<" {
+            value.checked_shl(shift)?
+        } else {
+            value.checked_shr(shift)?
+        };
+    }
+
+    Some(value)
+}
+
+fn parse_primary_expr<'a, I>(tokens: &mut Peekable<I>, known: &[(String, i128)]) -> Option<i128>
+where
+    I: Iterator<Item = &'a str>,
+{
+    match tokens.next()? {
+        "(" => {
+            let value = parse_or_expr(tokens, known)?;
+            tokens.next_if_eq(&")")?;
+            Some(value)
+        }
+        "-" => parse_primary_expr(tokens, known)?.checked_neg(),
+        token => parse_int_literal(token).or_else(|| {
+            known
+                .iter()
+                .rev()
+                .find(|(name, _)| name == token)
+                .map(|(_, value)| *value)
+        }),
+    }
+}
+
+// Parses a decimal or hex integer literal, e.g. `42`, `-1` or `0xFF`.
+fn parse_int_literal(literal: &str) -> Option<i128> {
+    let literal = literal.trim();
+    match literal
+        .strip_prefix("0x")
+        .or_else(|| literal.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok().map(i128::from),
+        None => literal.parse().ok(),
+    }
+}
+
+// Splits (Doxygen) block comments into their logical lines, stripping the
+// comment delimiters and leading `*`, e.g. `/** Validates the mnemonic. */` ->
+// `Validates the mnemonic.`. Line comments are kept as is, minus the `//`.
+fn normalize_comments(comments: &[String]) -> Vec<String> {
+    comments
+        .iter()
+        .flat_map(|comment| comment.lines())
+        .map(|line| {
+            let line = line.trim();
+            let line = line
+                .strip_prefix("/**")
+                .or_else(|| line.strip_prefix("/*"))
+                .or_else(|| line.strip_prefix("///"))
+                .or_else(|| line.strip_prefix("//"))
+                .unwrap_or(line);
+            let line = line.strip_suffix("*/").unwrap_or(line).trim();
+            line.strip_prefix('*').unwrap_or(line).trim().to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// The constructors and the destructor of a struct, e.g. for generating
+/// RAII wrappers or `Dispose` implementations.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Lifecycle {
+    pub ty: String,
+    pub constructors: Vec<String>,
+    pub destructor: Option<String>,
+}
+
+/// Groups the inits and deinits of the file by their associated struct, in
+/// declaration order of the structs. Structs without any init or deinit are
+/// omitted.
+pub fn pair_lifecycle(file: &FileInfo) -> Vec<Lifecycle> {
+    let is_associated = |name: &str, ty: &str| file.associated_type(name) == Some(ty);
+
+    file.structs
+        .iter()
+        .map(|strct| Lifecycle {
+            ty: strct.name.clone(),
+            constructors: file
+                .inits
+                .iter()
+                .filter(|init| is_associated(&init.name, &strct.name))
+                .map(|init| init.name.clone())
+                .collect(),
+            destructor: file
+                .deinits
+                .iter()
+                .find(|deinit| is_associated(&deinit.name, &strct.name))
+                .map(|deinit| deinit.name.clone()),
+        })
+        .filter(|lifecycle| !lifecycle.constructors.is_empty() || lifecycle.destructor.is_some())
+        .collect()
+}
+
+/// Resolves the imports of the given file against the directory of C headers,
+/// returning the absolute path of each imported header. Import paths are
+/// relative to that directory, e.g. `TWData.h` or `../TrustWalletCore/TWData.h`.
+/// System includes, such as `<stdint.h>`, are skipped.
+pub fn resolve_imports<P: AsRef<Path>>(dir: P, file: &FileInfo) -> Result<Vec<PathBuf>> {
+    let dir = dir.as_ref();
+
+    let mut resolved = vec![];
+    for import in file.imports.iter().filter(|import| !import.is_system) {
+        // Normalize the path, resolving `.` and `..` segments.
+        let mut path = PathBuf::new();
+        for component in dir.join(import.to_path()).components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir
+                    if matches!(path.components().next_back(), Some(Component::Normal(_))) =>
+                {
+                    path.pop();
+                }
+                _ => path.push(component),
+            }
+        }
+
+        if !path.is_file() {
+            return Err(Error::BadImport(format!(
+                "{} imports {}, which does not exist in {}",
+                file.name,
+                import.path.join("/"),
+                dir.display()
+            )));
+        }
+
+        resolved.push(fs::canonicalize(path)?);
+    }
+
+    Ok(resolved)
+}
+
+/// Detects circular imports between the given files, whose headers are
+/// located in the directory of C headers. Each cycle lists the headers in
+/// traversal order, e.g. `[A.h, B.h]` if `A.h` and `B.h` import each other.
+pub fn detect_import_cycles<P: AsRef<Path>>(
+    dir: P,
+    files: &[FileInfo],
+) -> Result<Vec<Vec<PathBuf>>> {
+    let graph = import_graph(dir.as_ref(), files)?;
+
+    fn visit<'a>(
+        node: &'a PathBuf,
+        graph: &'a [(PathBuf, Vec<PathBuf>)],
+        visited: &mut Vec<&'a PathBuf>,
+        stack: &mut Vec<&'a PathBuf>,
+        cycles: &mut Vec<Vec<PathBuf>>,
+    ) {
+        if let Some(pos) = stack.iter().position(|n| *n == node) {
+            cycles.push(stack[pos..].iter().map(|n| n.to_path_buf()).collect());
+            return;
+        }
+
+        if visited.contains(&node) {
+            return;
+        }
+
+        visited.push(node);
+        stack.push(node);
+
+        // Imported headers without a manifest file have no imports.
+        if let Some((_, imports)) = graph.iter().find(|(header, _)| header == node) {
+            for import in imports {
+                visit(import, graph, visited, stack, cycles);
+            }
+        }
+
+        stack.pop();
+    }
+
+    let (mut visited, mut cycles) = (vec![], vec![]);
+    for (header, _) in &graph {
+        visit(header, &graph, &mut visited, &mut vec![], &mut cycles);
+    }
+
+    Ok(cycles)
+}
+
+/// Orders the headers of the given files such that every header comes after
+/// the headers it imports, e.g. for targets which require a type to be defined
+/// before it is used. Headers without imports come first, ties keep the order
+/// of the files. Returns an error if circular imports prevent an ordering.
+pub fn topo_order<P: AsRef<Path>>(dir: P, files: &[FileInfo]) -> Result<Vec<PathBuf>> {
+    let graph = import_graph(dir.as_ref(), files)?;
+
+    // The number of imports of each header which are not ordered yet.
+    // Imported headers without a manifest file are not part of the ordering.
+    let mut pending = graph
+        .iter()
+        .map(|(_, imports)| {
+            imports
+                .iter()
+                .filter(|import| graph.iter().any(|(header, _)| header == *import))
+                .count()
+        })
+        .collect::<Vec<_>>();
+
+    // Kahn's algorithm.
+    let mut queue = (0..graph.len())
+        .filter(|&idx| pending[idx] == 0)
+        .collect::<VecDeque<_>>();
+    let mut order = vec![];
+    while let Some(idx) = queue.pop_front() {
+        let header = &graph[idx].0;
+        for (other, (_, imports)) in graph.iter().enumerate() {
+            for _ in imports.iter().filter(|import| *import == header) {
+                pending[other] -= 1;
+                if pending[other] == 0 {
+                    queue.push_back(other);
+                }
+            }
+        }
+
+        order.push(header.clone());
+    }
+
+    if order.len() < graph.len() {
+        let unordered = graph
+            .iter()
+            .filter(|(header, _)| !order.contains(header))
+            .map(|(header, _)| header.display().to_string())
+            .collect::<Vec<_>>();
+
+        return Err(Error::BadImport(format!(
+            "circular imports prevent ordering {}",
+            unordered.join(", ")
+        )));
+    }
+
+    Ok(order)
+}
+
+// Builds the import graph, keyed by the header of each file.
+fn import_graph(dir: &Path, files: &[FileInfo]) -> Result<Vec<(PathBuf, Vec<PathBuf>)>> {
+    let mut graph = vec![];
+    for file in files {
+        let header = fs::canonicalize(dir.join(format!("{}.h", file.name)))?;
+        graph.push((header, resolve_imports(dir, file)?));
+    }
+
+    Ok(graph)
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TypeInfo {
     #[serde(flatten)]
     pub variant: TypeVariant,
+    // Whether the value, respectively the pointee, is constant, e.g.
+    // `const uint8_t*`.
     pub is_constant: bool,
     pub is_nullable: bool,
-    pub is_pointer: bool,
+    // The number of pointer indirections, e.g. `2` for `TWData**`.
+    pub pointer_depth: u8,
+    // Whether the (outermost) pointer itself is constant, e.g.
+    // `uint8_t* const`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_pointer_const: bool,
 }
 
+impl TypeInfo {
+    pub fn is_pointer(&self) -> bool {
+        self.pointer_depth > 0
+    }
+
+    /// Whether this is a plain `void`, which has no value. A `void*` is an
+    /// opaque pointer instead.
+    pub fn is_unit(&self) -> bool {
+        self.variant == TypeVariant::Void && !self.is_pointer()
+    }
+
+    /// The ownership of a returned value of this type, by convention: opaque
+    /// wallet-core objects, such as `TWString*`, are handed over to the
+    /// caller, other pointers are borrowed.
+    pub fn return_ownership(&self) -> Ownership {
+        if !self.is_pointer() {
+            return Ownership::None;
+        }
+
+        match self.variant {
+            TypeVariant::String | TypeVariant::Data | TypeVariant::Struct(_) => Ownership::Owned,
+            _ => Ownership::Borrowed,
+        }
+    }
+}
+
+/// Who releases a returned pointer.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ownership {
+    /// The caller must delete it, e.g. via `TWStringDelete`.
+    Owned,
+    /// The callee keeps it, the caller must not delete it.
+    Borrowed,
+    /// Not a pointer, e.g. `bool` or `void`.
+    None,
+}
+
+impl TypeVariant {
+    // Replaces references to any of the `BYTE_TYPES` with `TypeVariant::Data`.
+    fn map_byte_types(&mut self) {
+        match self {
+            TypeVariant::Struct(name) if BYTE_TYPES.contains(&name.as_str()) => {
+                *self = TypeVariant::Data;
+            }
+            TypeVariant::Array { element, .. } => element.map_byte_types(),
+            TypeVariant::FunctionPointer { ret, params } => {
+                for ty in std::iter::once(ret.as_mut()).chain(params) {
+                    ty.variant.map_byte_types();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Returns the names of all structs and enums this type refers to,
+    // including array elements and function pointer signatures.
+    pub(crate) fn referenced_names(&self) -> Vec<&String> {
+        match self {
+            TypeVariant::Struct(name) | TypeVariant::Enum(name) => vec![name],
+            TypeVariant::Array { element, .. } => element.referenced_names(),
+            TypeVariant::FunctionPointer { ret, params } => std::iter::once(ret.as_ref())
+                .chain(params)
+                .flat_map(|ty| ty.variant.referenced_names())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    // Returns the raw keywords of all unknown types this type consists of.
+    fn unknown_names(&self) -> Vec<&String> {
+        match self {
+            TypeVariant::Unknown(raw) => vec![raw],
+            TypeVariant::Array { element, .. } => element.unknown_names(),
+            TypeVariant::FunctionPointer { ret, params } => std::iter::once(ret.as_ref())
+                .chain(params)
+                .flat_map(|ty| ty.variant.unknown_names())
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+// The derived implementations are wrapped by the ones below, which take care
+// of `TypeVariant::Unknown`.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(tag = "variant", content = "value", rename_all = "snake_case")]
+#[serde(
+    remote = "Self",
+    tag = "variant",
+    content = "value",
+    rename_all = "snake_case"
+)]
 pub enum TypeVariant {
     Void,
+    // `bool` from `<stdbool.h>` is the `_Bool` keyword.
+    #[serde(alias = "_Bool")]
     Bool,
     Char,
+    // Multi-word specifiers, such as `unsigned int`, are joined by
+    // underscores.
+    #[serde(alias = "short")]
     ShortInt,
     Int,
+    #[serde(alias = "unsigned")]
     UnsignedInt,
+    // `long` is platform-dependent, 32 or 64 bits.
+    #[serde(alias = "long")]
     LongInt,
+    #[serde(alias = "unsigned_long")]
+    UnsignedLongInt,
     Float,
     Double,
+    // Unsigned pointer-sized integers, such as `size_t` or `uintptr_t`.
+    #[serde(alias = "uintptr_t")]
     SizeT,
+    // Signed pointer-sized integers, such as `intptr_t` or `ssize_t`.
+    #[serde(rename = "intptr_t", alias = "ssize_t")]
+    IntPtr,
+    // `signed char` is a byte, unlike plain `char`.
+    #[serde(alias = "signed_char")]
     Int8T,
     Int16T,
     Int32T,
+    // `long long` is at least 64 bits on all supported platforms.
+    #[serde(alias = "long_long")]
     Int64T,
+    // As is `unsigned char`, e.g. for byte buffers.
+    #[serde(alias = "unsigned_char")]
     UInt8T,
     UInt16T,
     UInt32T,
+    #[serde(alias = "unsigned_long_long")]
     UInt64T,
     Struct(String),
     Enum(String),
     Data,
     String,
+    // A NUL-terminated `const char*`, as opposed to a `TWString*`. The pointer
+    // is part of the variant, e.g. for the elements of `const char* names[]`.
+    CString,
+    // A C array, such as `uint8_t key[32]`. The length is `None` for unsized
+    // arrays, such as `uint8_t data[]`. The elements are values; arrays of C
+    // strings, such as `const char* names[]`, have `CString` elements, the `*`
+    // binding to the element rather than to the array.
+    Array {
+        element: Box<TypeVariant>,
+        len: Option<usize>,
+    },
+    // A C function pointer, such as `void (*callback)(const void*)`.
+    FunctionPointer {
+        ret: Box<TypeInfo>,
+        params: Vec<TypeInfo>,
+    },
+    // A type which could not be classified, carrying the raw keyword, e.g.
+    // `variant: Whatever`. Reported by `FileInfo::unknown_types`.
+    #[serde(skip)]
+    Unknown(String),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl serde::Serialize for TypeVariant {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        match self {
+            // Written back as the raw keyword, so it round-trips.
+            TypeVariant::Unknown(raw) => {
+                let mut state = serializer.serialize_struct("TypeVariant", 1)?;
+                state.serialize_field("variant", raw)?;
+                state.end()
+            }
+            _ => TypeVariant::serialize(self, serializer),
+        }
+    }
+}
+
+/// The `TypeVariant` names without a value, including their aliases. Must be
+/// kept in sync with the `TypeVariant` declaration.
+pub(crate) const SCALAR_VARIANTS: &[&str] = &[
+    "void",
+    "bool",
+    "_Bool",
+    "char",
+    "short_int",
+    "short",
+    "int",
+    "unsigned_int",
+    "unsigned",
+    "long_int",
+    "long",
+    "unsigned_long_int",
+    "unsigned_long",
+    "float",
+    "double",
+    "size_t",
+    "uintptr_t",
+    "intptr_t",
+    "ssize_t",
+    "int8_t",
+    "signed_char",
+    "int16_t",
+    "int32_t",
+    "int64_t",
+    "long_long",
+    "u_int8_t",
+    "unsigned_char",
+    "u_int16_t",
+    "u_int32_t",
+    "u_int64_t",
+    "unsigned_long_long",
+    "data",
+    "string",
+    "c_string",
+];
+
+/// The `TypeVariant` names which carry a value.
+pub(crate) const VALUE_VARIANTS: &[&str] = &["struct", "enum", "array", "function_pointer"];
+
+impl<'de> serde::Deserialize<'de> for TypeVariant {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let value = serde_yaml::Value::deserialize(deserializer)?;
+
+        // Only unrecognized keywords fall back to `Unknown`, malformed values
+        // of known variants are still rejected.
+        match value.get("variant").and_then(serde_yaml::Value::as_str) {
+            Some(tag) if !SCALAR_VARIANTS.contains(&tag) && !VALUE_VARIANTS.contains(&tag) => {
+                Ok(TypeVariant::Unknown(tag.to_string()))
+            }
+            _ => TypeVariant::deserialize(value).map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct FileInfo {
     pub name: String,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imports: Vec<ImportInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub structs: Vec<StructInfo>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub inits: Vec<InitInfo>,
@@ -91,45 +1489,420 @@ pub struct FileInfo {
     pub properties: Vec<PropertyInfo>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub protos: Vec<ProtoInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub typedefs: Vec<TypedefInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constants: Vec<ConstantInfo>,
+    // The manifest file this info was parsed from, and the hash of its
+    // content, for tracing generated bindings back to their source. `None` if
+    // parsed from a string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_from_hash: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl FileInfo {
+    /// Returns the struct or enum of this file the given function name belongs
+    /// to, based on the longest matching name prefix, e.g. `TWMnemonicIsValid`
+    /// -> `TWMnemonic`.
+    pub fn associated_type(&self, name: &str) -> Option<&str> {
+        self.structs
+            .iter()
+            .map(|s| s.name.as_str())
+            .chain(self.enums.iter().map(|e| e.name.as_str()))
+            .filter(|ty| name.starts_with(ty))
+            .max_by_key(|ty| ty.len())
+    }
+
+    /// Sorts the top-level items of each category by name, for deterministic
+    /// output. Enum variants and parameters keep their declared order.
+    pub fn sort_items(&mut self) {
+        self.structs.sort_by(|a, b| a.name.cmp(&b.name));
+        self.inits.sort_by(|a, b| a.name.cmp(&b.name));
+        self.deinits.sort_by(|a, b| a.name.cmp(&b.name));
+        self.enums.sort_by(|a, b| a.name.cmp(&b.name));
+        self.functions.sort_by(|a, b| a.name.cmp(&b.name));
+        self.properties.sort_by(|a, b| a.name.cmp(&b.name));
+        self.typedefs.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    /// Returns the names of all structs and enums which are referenced by this
+    /// file, but not declared by it, in order of their first reference. Known
+    /// opaque types can be excluded via the allowlist. Types defined by other
+    /// files can be looked up via `Manifest::defining_file`.
+    pub fn unresolved_types(&self, allowlist: &[&str]) -> Vec<String> {
+        let is_declared = |name: &String| {
+            self.structs.iter().any(|s| &s.name == name)
+                || self.enums.iter().any(|e| &e.name == name)
+                || self.typedefs.iter().any(|t| &t.name == name)
+        };
+
+        let mut unresolved = vec![];
+        for name in self.types().flat_map(|ty| ty.variant.referenced_names()) {
+            if is_declared(name) || allowlist.contains(&name.as_str()) {
+                continue;
+            }
+
+            if !unresolved.contains(name) {
+                unresolved.push(name.clone());
+            }
+        }
+
+        unresolved
+    }
+
+    /// Returns the raw keywords of all types which could not be classified,
+    /// in order of their first occurrence. Generators keep or skip those
+    /// types as they see fit.
+    pub fn unknown_types(&self) -> Vec<String> {
+        let mut unknown = vec![];
+        for raw in self.types().flat_map(|ty| ty.variant.unknown_names()) {
+            if !unknown.contains(raw) {
+                unknown.push(raw.clone());
+            }
+        }
+
+        unknown
+    }
+
+    /// Returns all types referenced by the declarations of this file.
+    pub fn types(&self) -> impl Iterator<Item = &TypeInfo> {
+        let fields = self
+            .structs
+            .iter()
+            .flat_map(|s| s.fields.iter().map(|f| &f.ty));
+        let inits = self
+            .inits
+            .iter()
+            .flat_map(|i| i.params.iter().map(|p| &p.ty));
+        let functions = self.functions.iter().flat_map(|f| {
+            f.params
+                .iter()
+                .map(|p| &p.ty)
+                .chain(std::iter::once(&f.return_type))
+        });
+        let properties = self.properties.iter().map(|p| &p.return_type);
+        let typedefs = self.typedefs.iter().map(|t| &t.underlying);
+
+        fields
+            .chain(inits)
+            .chain(functions)
+            .chain(properties)
+            .chain(typedefs)
+    }
+
+    // Mutable counterpart of `types`.
+    fn types_mut(&mut self) -> impl Iterator<Item = &mut TypeInfo> {
+        let fields = self
+            .structs
+            .iter_mut()
+            .flat_map(|s| s.fields.iter_mut().map(|f| &mut f.ty));
+        let inits = self
+            .inits
+            .iter_mut()
+            .flat_map(|i| i.params.iter_mut().map(|p| &mut p.ty));
+        let functions = self.functions.iter_mut().flat_map(|f| {
+            f.params
+                .iter_mut()
+                .map(|p| &mut p.ty)
+                .chain(std::iter::once(&mut f.return_type))
+        });
+        let properties = self.properties.iter_mut().map(|p| &mut p.return_type);
+        let typedefs = self.typedefs.iter_mut().map(|t| &mut t.underlying);
+
+        fields
+            .chain(inits)
+            .chain(functions)
+            .chain(properties)
+            .chain(typedefs)
+    }
+
+    /// Flattens the type if it refers to one of the typedefs of this file,
+    /// e.g. `TWStoredKeyEncryption` -> `uint64_t`, returning the underlying
+    /// type. Other types are returned as is.
+    pub fn resolve_typedef(&self, ty: &TypeInfo) -> TypeInfo {
+        self.resolve_typedef_inner(ty, &mut vec![])
+    }
+
+    fn resolve_typedef_inner<'a>(&'a self, ty: &TypeInfo, visited: &mut Vec<&'a str>) -> TypeInfo {
+        let name = match &ty.variant {
+            TypeVariant::Struct(name) | TypeVariant::Enum(name) => name,
+            _ => return ty.clone(),
+        };
+
+        // Aliases such as `typedef struct TWFoo TWFoo;` refer to themselves.
+        let Some(typedef) = self
+            .typedefs
+            .iter()
+            .find(|t| &t.name == name && !visited.contains(&t.name.as_str()))
+        else {
+            return ty.clone();
+        };
+
+        visited.push(&typedef.name);
+
+        // E.g. for `typedef struct TWFooImpl* TWFoo`, a `TWFoo*` is a
+        // `TWFooImpl**`.
+        let underlying = self.resolve_typedef_inner(&typedef.underlying, visited);
+        TypeInfo {
+            variant: underlying.variant,
+            is_constant: ty.is_constant || underlying.is_constant,
+            is_nullable: ty.is_nullable,
+            pointer_depth: ty.pointer_depth + underlying.pointer_depth,
+            // The outermost pointer belongs to the alias, if it is a pointer.
+            is_pointer_const: if ty.is_pointer() {
+                ty.is_pointer_const
+            } else {
+                underlying.is_pointer_const
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ImportInfo {
     // Expressed as directories plus the final file.
     // E.g. `to/some/file.h` ~= ["to", "some", "file.h"]
     pub path: Vec<String>,
+    // Whether the header is included as `<file.h>` rather than `"file.h"`.
+    #[serde(default)]
+    pub is_system: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl ImportInfo {
+    /// Returns the relative path of the imported header.
+    pub fn to_path(&self) -> PathBuf {
+        self.path.iter().flat_map(|part| split_path(part)).collect()
+    }
+}
+
+// Splits a path into its components, accepting both `/` and `\` separators,
+// e.g. `..\TrustWalletCore\TWData.h` ~= ["..", "TrustWalletCore", "TWData.h"].
+fn split_path(path: &str) -> Vec<String> {
+    Path::new(&path.replace('\\', "/"))
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect()
+}
+
+/// Parses an include directive, e.g. `#include <stdint.h>` or
+/// `#include "TWData.h"`.
+impl std::str::FromStr for ImportInfo {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let bad_format = || Error::BadFormat(format!("invalid include directive: {line}"));
+
+        let target = line
+            .trim()
+            .strip_prefix('#')
+            .map(str::trim_start)
+            .and_then(|line| line.strip_prefix("include"))
+            .map(str::trim)
+            .ok_or_else(bad_format)?;
+
+        let (path, is_system) = if let Some(path) = target
+            .strip_prefix('<')
+            .and_then(|path| path.strip_suffix('>'))
+        {
+            (path, true)
+        } else if let Some(path) = target
+            .strip_prefix('"')
+            .and_then(|path| path.strip_suffix('"'))
+        {
+            (path, false)
+        } else {
+            return Err(bad_format());
+        };
+
+        if path.is_empty() {
+            return Err(bad_format());
+        }
+
+        Ok(ImportInfo {
+            path: split_path(path),
+            is_system,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ProtoInfo(pub String);
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A standalone constant, e.g. `#define TW_HD_VERSION 3`. The value is the
+/// literal as written, without the quotes of string literals.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ConstantInfo {
+    pub name: String,
+    pub value_type: TypeVariant,
+    pub value: String,
+}
+
+/// Parses an object-like macro defining an integer or string literal, e.g.
+/// `#define TW_X 42` or `#define TW_NAME "name"`. Other macros, such as
+/// function-like macros, are rejected.
+impl std::str::FromStr for ConstantInfo {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let bad_format = || Error::BadFormat(format!("not a constant definition: {line}"));
+
+        let (name, value) = line
+            .trim()
+            .strip_prefix('#')
+            .map(str::trim_start)
+            .and_then(|line| line.strip_prefix("define"))
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+            .and_then(|rest| rest.trim().split_once(char::is_whitespace))
+            .map(|(name, value)| (name, value.trim()))
+            .ok_or_else(bad_format)?;
+
+        // Function-like macros have a parameter list directly after the name.
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(bad_format());
+        }
+
+        let (value_type, value) = if let Some(string) = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+        {
+            (TypeVariant::String, string)
+        } else if is_integer_literal(value) {
+            (TypeVariant::Int, value)
+        } else {
+            return Err(bad_format());
+        };
+
+        Ok(ConstantInfo {
+            name: name.to_string(),
+            value_type,
+            value: value.to_string(),
+        })
+    }
+}
+
+// Whether the value is a decimal or hexadecimal integer literal, optionally
+// negative or with `U`/`L` suffixes, e.g. `-1`, `0x80000000UL`.
+fn is_integer_literal(value: &str) -> bool {
+    let value = value.strip_prefix('-').unwrap_or(value);
+    let value = value.trim_end_matches(['u', 'U', 'l', 'L']);
+
+    if let Some(hex) = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit())
+    } else {
+        !value.is_empty() && value.chars().all(|c| c.is_ascii_digit())
+    }
+}
+
+/// A type alias, e.g. `typedef uint64_t TWStoredKeyEncryption;`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TypedefInfo {
+    pub name: String,
+    pub underlying: TypeInfo,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct EnumInfo {
     pub name: String,
     pub is_public: bool,
     pub value_type: TypeVariant,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub variants: Vec<EnumVariantInfo>,
+    // The manifest file declaring this item, set when parsing a file, e.g.
+    // `TWMnemonic.yaml`. Kept when files are merged into a combined manifest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defined_in: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct EnumVariantInfo {
     pub name: String,
     pub value: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub as_string: Option<String>,
+    // A preceding block comment and a trailing line comment, in that order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct StructInfo {
     pub name: String,
     pub is_public: bool,
     pub is_class: bool,
+    #[serde(default)]
+    pub is_deprecated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_note: Option<String>,
+    // NOTE: The field order is ABI-significant, e.g. for `#[repr(C)]`
+    // structs, and must follow the declaration order. Do not collect the
+    // fields into a map or sort them.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub fields: Vec<(String, TypeInfo)>,
+    pub fields: Vec<FieldInfo>,
+    // The manifest file declaring this item, set when parsing a file, e.g.
+    // `TWMnemonic.yaml`. Kept when files are merged into a combined manifest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defined_in: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RawFieldInfo")]
+pub struct FieldInfo {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: TypeInfo,
+    // The width of bitfields, e.g. `1` for `uint32_t flag : 1`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bit_width: Option<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<String>,
+}
+
+// Older manifest files declare fields as `[name, type]` pairs.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawFieldInfo {
+    Pair(String, TypeInfo),
+    Field {
+        name: String,
+        #[serde(rename = "type")]
+        ty: TypeInfo,
+        #[serde(default)]
+        bit_width: Option<u8>,
+        #[serde(default)]
+        comments: Vec<String>,
+    },
+}
+
+impl From<RawFieldInfo> for FieldInfo {
+    fn from(raw: RawFieldInfo) -> Self {
+        match raw {
+            RawFieldInfo::Pair(name, ty) => FieldInfo {
+                name,
+                ty,
+                bit_width: None,
+                comments: vec![],
+            },
+            RawFieldInfo::Field {
+                name,
+                ty,
+                bit_width,
+                comments,
+            } => FieldInfo {
+                name,
+                ty,
+                bit_width,
+                comments,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct InitInfo {
     pub name: String,
     pub is_public: bool,
@@ -140,34 +1913,192 @@ pub struct InitInfo {
     pub comments: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct DeinitInfo {
     pub name: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct FunctionInfo {
     pub name: String,
     pub is_public: bool,
     pub is_static: bool,
+    // The struct or enum this function belongs to, e.g. `TWMnemonic` for
+    // `TWMnemonicIsValid`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub associated_type: Option<String>,
+    #[serde(default)]
+    pub is_deprecated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_note: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub params: Vec<ParamInfo>,
+    // Whether the parameters end with `...`, which is not part of `params`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_variadic: bool,
     pub return_type: TypeInfo,
+    // Overrides the ownership of the returned value, see
+    // `TypeInfo::return_ownership`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ownership: Option<Ownership>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub comments: Vec<String>,
+    // The manifest file declaring this item, set when parsing a file, e.g.
+    // `TWMnemonic.yaml`. Kept when files are merged into a combined manifest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defined_in: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl FunctionInfo {
+    /// Returns the ownership of the returned value, unless overridden as
+    /// inferred from the return type.
+    pub fn return_ownership(&self) -> Ownership {
+        self.ownership
+            .unwrap_or_else(|| self.return_type.return_ownership())
+    }
+
+    /// Whether the function belongs to the given struct or enum. Falls back to
+    /// the name prefix if no associated type is known.
+    pub fn is_associated_with(&self, object_name: &str) -> bool {
+        match &self.associated_type {
+            Some(ty) => ty == object_name,
+            None => self.name.starts_with(object_name),
+        }
+    }
+
+    /// Returns the structured documentation of the function, based on the
+    /// Doxygen tags of its comments.
+    pub fn doc(&self) -> NormalizedComment {
+        normalize_comment(&self.comments.join("\n"))
+    }
+
+    /// Returns a hash of the signature, i.e. the name, the parameter types
+    /// and the return type, for detecting ABI changes between builds.
+    /// Parameter names and comments are not part of the signature.
+    pub fn signature_hash(&self) -> u64 {
+        let mut signature = self.name.clone();
+        for ty in self.params.iter().map(|p| &p.ty).chain([&self.return_type]) {
+            signature.push('|');
+            signature.push_str(&serde_json::to_string(ty).unwrap_or_default());
+        }
+        if self.is_variadic {
+            signature.push_str("|...");
+        }
+
+        stable_hash(signature.as_bytes())
+    }
+}
+
+/// Documentation extracted from a Doxygen comment.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct NormalizedComment {
+    pub brief: Option<String>,
+    // Maps each parameter name to its description.
+    pub params: HashMap<String, String>,
+    pub returns: Option<String>,
+}
+
+/// Extracts the `brief`, `param` and `return` sections of a Doxygen comment,
+/// supporting both `\tag` and `@tag` notations. Untagged text at the start is
+/// treated as the brief, and lines without a tag continue the previous
+/// section.
+pub fn normalize_comment(raw: &str) -> NormalizedComment {
+    enum Section {
+        Brief,
+        Param(String),
+        Returns,
+        // Unsupported tags, e.g. `@see`.
+        Other,
+    }
+
+    fn append(target: &mut Option<String>, text: &str) {
+        match target {
+            Some(existing) if !text.is_empty() => {
+                existing.push(' ');
+                existing.push_str(text);
+            }
+            Some(_) => {}
+            None => *target = Some(text.to_string()),
+        }
+    }
+
+    let mut doc = NormalizedComment::default();
+    let mut section = Section::Brief;
+
+    for line in raw.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (tag, rest) = match line.strip_prefix(['\\', '@']) {
+            Some(tagged) => {
+                let (tag, rest) = tagged
+                    .split_once(char::is_whitespace)
+                    .unwrap_or((tagged, ""));
+                (Some(tag), rest.trim())
+            }
+            None => (None, line),
+        };
+
+        let text = match tag {
+            None => rest,
+            Some("brief" | "short") => {
+                section = Section::Brief;
+                rest
+            }
+            // E.g. `@param coin The coin type` or `@param[in] coin ...`.
+            Some(tag) if tag.starts_with("param") => {
+                let (name, desc) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                section = Section::Param(name.to_string());
+                desc.trim()
+            }
+            Some("return" | "returns" | "retval") => {
+                section = Section::Returns;
+                rest
+            }
+            Some(_) => {
+                section = Section::Other;
+                rest
+            }
+        };
+
+        match &section {
+            Section::Brief => append(&mut doc.brief, text),
+            Section::Param(name) => {
+                let mut desc = doc.params.remove(name);
+                append(&mut desc, text);
+                doc.params.insert(name.clone(), desc.unwrap_or_default());
+            }
+            Section::Returns => append(&mut doc.returns, text),
+            Section::Other => {}
+        }
+    }
+
+    doc
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PropertyInfo {
     pub name: String,
     pub is_public: bool,
     pub return_type: TypeInfo,
+    // Overrides the ownership of the returned value, see
+    // `TypeInfo::return_ownership`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ownership: Option<Ownership>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub comments: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl PropertyInfo {
+    /// Returns the ownership of the returned value, unless overridden as
+    /// inferred from the return type.
+    pub fn return_ownership(&self) -> Ownership {
+        self.ownership
+            .unwrap_or_else(|| self.return_type.return_ownership())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ParamInfo {
+    // Unnamed parameters are named by their position, e.g. `arg0`.
+    #[serde(default)]
     pub name: String,
     #[serde(rename = "type")]
     pub ty: TypeInfo,
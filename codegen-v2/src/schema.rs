@@ -0,0 +1,196 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::manifest::{SCALAR_VARIANTS, VALUE_VARIANTS};
+use serde_json::{json, Value};
+
+/// Returns a JSON Schema (draft-07) describing the JSON representation of a
+/// `FileInfo`, as written by `write_manifests`, so that external tools can
+/// validate the manifest files they load. Any other variant name is accepted
+/// as an unknown type, just like the parser does.
+pub fn manifest_json_schema() -> Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "FileInfo",
+        "$ref": "#/definitions/FileInfo",
+        "definitions": {
+            "FileInfo": object(&["name"], json!({
+                "name": string(),
+                "imports": array_of("ImportInfo"),
+                "structs": array_of("StructInfo"),
+                "inits": array_of("InitInfo"),
+                "deinits": array_of("DeinitInfo"),
+                "enums": array_of("EnumInfo"),
+                "functions": array_of("FunctionInfo"),
+                "properties": array_of("PropertyInfo"),
+                "protos": { "type": "array", "items": string() },
+                "typedefs": array_of("TypedefInfo"),
+                "constants": array_of("ConstantInfo"),
+                "source_path": string(),
+                "generated_from_hash": string(),
+            })),
+            "ImportInfo": object(&["path"], json!({
+                "path": { "type": "array", "items": string() },
+                "is_system": boolean(),
+            })),
+            "TypedefInfo": object(&["name", "underlying"], json!({
+                "name": string(),
+                "underlying": reference("TypeInfo"),
+            })),
+            "ConstantInfo": object(&["name", "value_type", "value"], json!({
+                "name": string(),
+                "value_type": reference("TypeVariant"),
+                "value": string(),
+            })),
+            "EnumInfo": object(&["name", "is_public", "value_type"], json!({
+                "name": string(),
+                "is_public": boolean(),
+                "value_type": reference("TypeVariant"),
+                "variants": array_of("EnumVariantInfo"),
+                "defined_in": string(),
+            })),
+            "EnumVariantInfo": object(&["name", "value"], json!({
+                "name": string(),
+                "value": string(),
+                "as_string": string(),
+                "comments": strings(),
+            })),
+            "StructInfo": object(&["name", "is_public", "is_class"], json!({
+                "name": string(),
+                "is_public": boolean(),
+                "is_class": boolean(),
+                "is_deprecated": boolean(),
+                "deprecation_note": string(),
+                "fields": array_of("FieldInfo"),
+                "defined_in": string(),
+            })),
+            // Older manifest files declare fields as `[name, type]` pairs.
+            "FieldInfo": {
+                "anyOf": [
+                    object(&["name", "type"], json!({
+                        "name": string(),
+                        "type": reference("TypeInfo"),
+                        "bit_width": { "type": "integer", "minimum": 0, "maximum": 255 },
+                        "comments": strings(),
+                    })),
+                    {
+                        "type": "array",
+                        "items": [string(), reference("TypeInfo")],
+                        "minItems": 2,
+                        "maxItems": 2,
+                    },
+                ],
+            },
+            "InitInfo": object(&["name", "is_public", "is_nullable"], json!({
+                "name": string(),
+                "is_public": boolean(),
+                "is_nullable": boolean(),
+                "params": array_of("ParamInfo"),
+                "comments": strings(),
+            })),
+            "DeinitInfo": object(&["name"], json!({
+                "name": string(),
+            })),
+            "FunctionInfo": object(&["name", "is_public", "is_static", "return_type"], json!({
+                "name": string(),
+                "is_public": boolean(),
+                "is_static": boolean(),
+                "associated_type": string(),
+                "is_deprecated": boolean(),
+                "deprecation_note": string(),
+                "params": array_of("ParamInfo"),
+                "is_variadic": boolean(),
+                "return_type": reference("TypeInfo"),
+                "ownership": { "enum": ["owned", "borrowed", "none"] },
+                "comments": strings(),
+                "defined_in": string(),
+            })),
+            "PropertyInfo": object(&["name", "is_public", "return_type"], json!({
+                "name": string(),
+                "is_public": boolean(),
+                "return_type": reference("TypeInfo"),
+                "ownership": { "enum": ["owned", "borrowed", "none"] },
+                "comments": strings(),
+            })),
+            "ParamInfo": object(&["type"], json!({
+                "name": string(),
+                "type": reference("TypeInfo"),
+            })),
+            // The variant is flattened into the type.
+            "TypeInfo": {
+                "allOf": [
+                    reference("TypeVariant"),
+                    object(&["is_constant", "is_nullable", "pointer_depth"], json!({
+                        "is_constant": boolean(),
+                        "is_nullable": boolean(),
+                        "pointer_depth": { "type": "integer", "minimum": 0, "maximum": 255 },
+                        "is_pointer_const": boolean(),
+                    })),
+                ],
+            },
+            "TypeVariant": {
+                "anyOf": [
+                    object(&["variant"], json!({
+                        "variant": { "enum": SCALAR_VARIANTS },
+                    })),
+                    object(&["variant", "value"], json!({
+                        "variant": { "enum": ["struct", "enum"] },
+                        "value": string(),
+                    })),
+                    object(&["variant", "value"], json!({
+                        "variant": { "enum": ["array"] },
+                        "value": object(&["element"], json!({
+                            "element": reference("TypeVariant"),
+                            "len": { "type": ["integer", "null"], "minimum": 0 },
+                        })),
+                    })),
+                    object(&["variant", "value"], json!({
+                        "variant": { "enum": ["function_pointer"] },
+                        "value": object(&["ret", "params"], json!({
+                            "ret": reference("TypeInfo"),
+                            "params": array_of("TypeInfo"),
+                        })),
+                    })),
+                    // Unknown types, e.g. `variant: Whatever`.
+                    object(&["variant"], json!({
+                        "variant": {
+                            "type": "string",
+                            "not": { "enum": SCALAR_VARIANTS.iter().chain(VALUE_VARIANTS).collect::<Vec<_>>() },
+                        },
+                    })),
+                ],
+            },
+        },
+    })
+}
+
+fn object(required: &[&str], properties: Value) -> Value {
+    json!({
+        "type": "object",
+        "required": required,
+        "properties": properties,
+    })
+}
+
+fn reference(definition: &str) -> Value {
+    json!({ "$ref": format!("#/definitions/{definition}") })
+}
+
+fn array_of(definition: &str) -> Value {
+    json!({ "type": "array", "items": reference(definition) })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn strings() -> Value {
+    json!({ "type": "array", "items": string() })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
@@ -0,0 +1,67 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::builder::{pointer, value, FileInfoBuilder};
+use crate::codegen::{generate, TargetLanguage};
+use crate::manifest::{parse_str, TypeVariant};
+
+#[test]
+fn build_file_info() {
+    let file_info = FileInfoBuilder::new("TWFoo")
+        .add_enum("TWFooKind", &[("first", 0), ("second", 1)])
+        .add_class("TWFoo")
+        .add_struct(
+            "TWFooPoint",
+            vec![
+                ("x", value(TypeVariant::Int32T)),
+                ("y", value(TypeVariant::Int32T)),
+            ],
+        )
+        .add_method(
+            "TWFooSign",
+            vec![
+                ("foo", pointer(TypeVariant::Struct("TWFoo".to_string()))),
+                ("digest", pointer(TypeVariant::Data)),
+            ],
+            pointer(TypeVariant::Data),
+        )
+        .add_static_method(
+            "TWFooIsValid",
+            vec![("name", pointer(TypeVariant::String))],
+            value(TypeVariant::Bool),
+        )
+        .add_property(
+            "TWFooKind",
+            value(TypeVariant::Enum("TWFooKind".to_string())),
+        )
+        .build();
+
+    assert_eq!(file_info.name, "TWFoo");
+
+    let variants = &file_info.enums[0].variants;
+    assert_eq!(variants[1].name, "second");
+    assert_eq!(variants[1].value, "1");
+
+    let [class, point] = &file_info.structs[..] else {
+        panic!("expected two structs");
+    };
+    assert!(class.is_class && class.fields.is_empty());
+    assert_eq!(point.fields[1].name, "y");
+
+    let [sign, is_valid] = &file_info.functions[..] else {
+        panic!("expected two functions");
+    };
+    assert!(!sign.is_static && is_valid.is_static);
+    assert_eq!(sign.params[1].ty.pointer_depth, 1);
+    assert_eq!(sign.associated_type.as_deref(), Some("TWFoo"));
+    assert_eq!(file_info.properties[0].return_type.pointer_depth, 0);
+
+    // The result is a valid manifest file.
+    let yaml = serde_yaml::to_string(&file_info).unwrap();
+    assert_eq!(parse_str(&yaml).unwrap(), file_info);
+    let output = generate(&file_info, TargetLanguage::TypeScript).unwrap();
+    assert!(output.contains("isValid"), "{output}");
+}
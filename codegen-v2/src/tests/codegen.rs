@@ -0,0 +1,180 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::with_current_year;
+use crate::codegen::{
+    generate, generate_file, generate_index, transform_name, NameStyle, TargetLanguage,
+};
+use crate::manifest::{parse_dir, parse_str, Manifest};
+use crate::Error;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn generate_all_targets() {
+    let file_info = parse_str(include_str!("samples/bindings.input.yaml")).unwrap();
+
+    for target in TargetLanguage::ALL {
+        match generate(&file_info, target) {
+            Ok(output) => assert!(output.contains("MainStruct"), "{target:?}"),
+            Err(Error::UnsupportedTarget(msg)) => {
+                assert!(matches!(
+                    target,
+                    TargetLanguage::ObjC | TargetLanguage::Swift
+                ));
+                assert!(msg.contains("Bindings"), "{msg}");
+            }
+            Err(err) => panic!("{target:?}: {err:?}"),
+        }
+    }
+}
+
+#[test]
+fn generate_file_all_targets() {
+    let file_info = parse_str(include_str!("samples/bindings.input.yaml")).unwrap();
+    let manifest = Manifest::from_files(vec![file_info.clone()]);
+
+    for target in TargetLanguage::ALL {
+        let outputs = generate_file(&file_info, &manifest, target).unwrap();
+        let declares = |(_path, output): &(PathBuf, String)| output.contains("MainStruct");
+        assert!(outputs.iter().any(declares), "{target:?}");
+    }
+
+    // Multi-file targets.
+    let paths = |target| {
+        generate_file(&file_info, &manifest, target)
+            .unwrap()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        paths(TargetLanguage::ObjC),
+        [PathBuf::from("Bindings.h"), PathBuf::from("Bindings.m")]
+    );
+    assert!(paths(TargetLanguage::Swift).contains(&PathBuf::from("MainStruct.swift")));
+}
+
+#[test]
+fn typescript_index() {
+    const EXPECTED: &str = include_str!("samples/index.output.d.ts");
+
+    let files = vec![
+        parse_str(include_str!("samples/bindings.input.yaml")).unwrap(),
+        parse_str(include_str!("samples/struct_kinds.input.yaml")).unwrap(),
+    ];
+
+    let output = generate_index(&files, TargetLanguage::TypeScript).unwrap();
+    println!("{output}");
+    assert_eq!(output, with_current_year(EXPECTED));
+
+    let err = generate_index(&files, TargetLanguage::Go).unwrap_err();
+    assert!(matches!(err, Error::UnsupportedTarget(_)));
+}
+
+#[test]
+fn name_styles() {
+    let name = |style| transform_name("TWMnemonicIsValidWord", Some("TWMnemonic"), style);
+    assert_eq!(name(NameStyle::LowerCamelCase), "isValidWord");
+    assert_eq!(name(NameStyle::UpperCamelCase), "IsValidWord");
+    assert_eq!(name(NameStyle::SnakeCase), "is_valid_word");
+    assert_eq!(name(NameStyle::ShoutySnakeCase), "IS_VALID_WORD");
+
+    // Names of other types are converted as they are.
+    assert_eq!(
+        transform_name("TWDataCreate", Some("TWMnemonic"), NameStyle::SnakeCase),
+        "tw_data_create"
+    );
+    assert_eq!(
+        transform_name("TWPublicKey", None, NameStyle::LowerCamelCase),
+        "twPublicKey"
+    );
+    assert_eq!(
+        transform_name("TWPublicKey", Some("TW"), NameStyle::SnakeCase),
+        "public_key"
+    );
+}
+
+#[test]
+fn python_index_loads_every_module() {
+    let files = vec![
+        parse_str(include_str!("samples/bindings.input.yaml")).unwrap(),
+        parse_str(include_str!("samples/struct_kinds.input.yaml")).unwrap(),
+    ];
+
+    // The star imports would otherwise only keep the `load` of the last
+    // module, leaving the library of the others unset.
+    let output = generate_index(&files, TargetLanguage::Python).unwrap();
+    println!("{output}");
+    for module in ["Bindings", "TWHandle"] {
+        assert!(output.contains(&format!("from . import {module} as _{module}\n")));
+        assert!(output.contains(&format!("    _{module}.load(path)\n")));
+    }
+    assert!(output.ends_with("    _TWHandle.load(path)\n"));
+}
+
+#[test]
+fn rust_index_resolves_modules() {
+    let files = parse_dir("manifest/").unwrap();
+    let dir = std::env::temp_dir().join(format!("codegen-v2-rust-index-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    for file in &files {
+        let rendered = generate(file, TargetLanguage::Rust).unwrap();
+        std::fs::write(dir.join(format!("{}.rs", file.name)), rendered).unwrap();
+    }
+    let index = generate_index(&files, TargetLanguage::Rust).unwrap();
+    std::fs::write(dir.join("lib.rs"), index).unwrap();
+
+    // Only checks the crate, the native library is not linked.
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let output = Command::new(rustc)
+        .args([
+            "--crate-type",
+            "lib",
+            "--edition",
+            "2021",
+            "--emit",
+            "metadata",
+        ])
+        .arg("--out-dir")
+        .arg(&dir)
+        .arg(dir.join("lib.rs"))
+        .output()
+        .unwrap();
+
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn c_string_array_elements() {
+    let file_info = parse_str(include_str!("samples/pointer_array.input.yaml")).unwrap();
+
+    // Unlike `TWString*`, the elements are plain C strings.
+    let expected = [
+        (
+            TargetLanguage::CSharp,
+            "TWFooJoin(string[] names, UIntPtr count)",
+        ),
+        (
+            TargetLanguage::Python,
+            "argtypes = [ctypes.POINTER(ctypes.c_char_p), ",
+        ),
+        (
+            TargetLanguage::Rust,
+            "TWFooJoin(names: *const *const c_char, count: usize)",
+        ),
+    ];
+    for (target, expected) in expected {
+        let output = generate(&file_info, target).unwrap();
+        assert!(output.contains(expected), "{target:?}: {output}");
+    }
+}
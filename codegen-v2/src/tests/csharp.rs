@@ -0,0 +1,46 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::with_current_year;
+use crate::codegen::csharp::{generate_csharp, generate_csharp_string_helper};
+use crate::manifest::parse_str;
+use crate::Error;
+
+#[test]
+fn csharp_bindings() {
+    const INPUT: &str = include_str!("samples/bindings.input.yaml");
+    const EXPECTED: &str = include_str!("samples/bindings.output.cs");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let output = generate_csharp(&file_info).unwrap();
+    println!("{output}");
+    assert_eq!(output, with_current_year(EXPECTED));
+}
+
+#[test]
+fn csharp_string_helper() {
+    let output = generate_csharp_string_helper().unwrap();
+    assert!(output.contains("public static class TWStringHelper"));
+    assert!(output.contains("TWStringCreateWithUTF8Bytes"));
+}
+
+#[test]
+fn csharp_enum_backing_type() {
+    let file_info = parse_str(include_str!("samples/enum_backing.input.yaml")).unwrap();
+    let output = generate_csharp(&file_info).unwrap();
+    assert!(output.contains("public enum TWWide : ulong"));
+}
+
+#[test]
+fn csharp_rejects_bitfields() {
+    let file_info = parse_str(include_str!("samples/bitfield.input.yaml")).unwrap();
+
+    // Widening the bitfields to their full type would break the layout.
+    match generate_csharp(&file_info).unwrap_err() {
+        Error::UnsupportedTarget(msg) => assert!(msg.contains("`flag` of TWFlags"), "{msg}"),
+        err => panic!("{err:?}"),
+    }
+}
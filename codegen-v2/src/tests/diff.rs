@@ -0,0 +1,118 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::diff::{diff_manifests, ChangeKind, ItemKind, ManifestChange};
+use crate::manifest::{build_manifest, EnumVariantInfo, TypeVariant};
+
+#[test]
+fn diff_identical_manifests() {
+    let manifest = build_manifest("manifest/").unwrap();
+    let diff = diff_manifests(&manifest, &manifest);
+
+    assert!(diff.changes.is_empty());
+    assert!(!diff.is_breaking());
+}
+
+#[test]
+fn diff_changed_manifests() {
+    let old = build_manifest("manifest/").unwrap();
+    let mut new = old.clone();
+
+    for file in &mut new.files {
+        file.functions
+            .retain(|func| func.name != "TWPrivateKeyIsValid");
+
+        for enm in file.enums.iter_mut().filter(|enm| enm.name == "TWCurve") {
+            enm.variants.push(EnumVariantInfo {
+                name: "newCurve".to_string(),
+                value: "100".to_string(),
+                as_string: None,
+                comments: vec![],
+            });
+        }
+    }
+
+    let diff = diff_manifests(&old, &new);
+    assert_eq!(
+        diff.changes,
+        vec![
+            ManifestChange {
+                kind: ChangeKind::Added,
+                item: ItemKind::EnumVariant,
+                name: "TWCurve.newCurve".to_string(),
+            },
+            ManifestChange {
+                kind: ChangeKind::Removed,
+                item: ItemKind::Function,
+                name: "TWPrivateKeyIsValid".to_string(),
+            },
+        ]
+    );
+    assert!(diff.is_breaking());
+    assert!(!diff.changes[0].is_breaking());
+}
+
+#[test]
+fn diff_signature_change() {
+    let old = build_manifest("manifest/").unwrap();
+    let mut new = old.clone();
+
+    for file in &mut new.files {
+        for func in file.functions.iter_mut() {
+            if func.name == "TWPrivateKeyIsValid" {
+                func.return_type.variant = TypeVariant::Int;
+            }
+            // Renaming parameters keeps the signature.
+            for param in &mut func.params {
+                param.name = format!("{}_", param.name);
+            }
+        }
+    }
+
+    let diff = diff_manifests(&old, &new);
+    assert_eq!(
+        diff.changes,
+        vec![ManifestChange {
+            kind: ChangeKind::Changed,
+            item: ItemKind::Function,
+            name: "TWPrivateKeyIsValid".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn diff_ignores_equivalent_changes() {
+    let old = build_manifest("manifest/").unwrap();
+    let mut new = old.clone();
+
+    for file in &mut new.files {
+        // Documenting fields keeps the struct.
+        for field in file.structs.iter_mut().flat_map(|s| &mut s.fields) {
+            field.comments.push("Documented.".to_string());
+        }
+
+        // Spelling the value differently keeps the variant.
+        for enm in file.enums.iter_mut().filter(|enm| enm.name == "TWCurve") {
+            assert_eq!(enm.variants[0].value, "0");
+            enm.variants[0].value = "0x00".to_string();
+        }
+    }
+
+    assert!(diff_manifests(&old, &new).changes.is_empty());
+
+    for file in &mut new.files {
+        for field in file.structs.iter_mut().flat_map(|s| &mut s.fields) {
+            field.bit_width = Some(1);
+        }
+    }
+
+    let diff = diff_manifests(&old, &new);
+    assert!(diff
+        .changes
+        .iter()
+        .all(|c| c.kind == ChangeKind::Changed && c.item == ItemKind::Struct));
+    assert!(diff.changes.iter().any(|c| c.name == "TWAES"));
+}
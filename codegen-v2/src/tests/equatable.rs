@@ -0,0 +1,68 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::codegen::equatable::generate_equatable;
+use crate::codegen::TargetLanguage;
+use crate::manifest::parse_str;
+
+#[test]
+fn equatable_value_struct() {
+    let file_info = parse_str(include_str!("samples/struct_kinds.input.yaml")).unwrap();
+    let [handle, point] = &file_info.structs[..] else {
+        panic!("expected two structs");
+    };
+
+    assert_eq!(
+        generate_equatable(point, TargetLanguage::Kotlin)
+            .unwrap()
+            .unwrap(),
+        "data class Point(
+    val x: Int,
+    val y: Int,
+)
+"
+    );
+    assert_eq!(
+        generate_equatable(point, TargetLanguage::Swift)
+            .unwrap()
+            .unwrap(),
+        "extension Point: Equatable, Hashable {
+    public static func == (lhs: Point, rhs: Point) -> Bool {
+        return lhs.x == rhs.x && lhs.y == rhs.y
+    }
+
+    public func hash(into hasher: inout Hasher) {
+        hasher.combine(x)
+        hasher.combine(y)
+    }
+}
+"
+    );
+
+    // Opaque structs are compared by identity.
+    assert!(generate_equatable(handle, TargetLanguage::Kotlin)
+        .unwrap()
+        .is_none());
+    assert!(generate_equatable(point, TargetLanguage::Protobuf)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn equatable_rust_keyword_fields() {
+    let mut file_info = parse_str(include_str!("samples/struct_kinds.input.yaml")).unwrap();
+    let point = &mut file_info.structs[1];
+    point.fields[0].name = "type".to_string();
+
+    let output = generate_equatable(point, TargetLanguage::Rust)
+        .unwrap()
+        .unwrap();
+    assert!(
+        output.contains("self.r#type == other.r#type && self.y == other.y"),
+        "{output}"
+    );
+    assert!(output.contains("std::hash::Hash::hash(&self.r#type, state);"));
+}
@@ -0,0 +1,38 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::Error;
+use handlebars::{Handlebars, RenderError};
+use std::error::Error as _;
+
+#[test]
+fn error_display() {
+    let errors = vec![
+        Error::from(std::io::Error::new(std::io::ErrorKind::NotFound, "gone")),
+        Error::from(serde_yaml::from_str::<u8>("[").unwrap_err()),
+        Error::from(serde_json::from_str::<u8>("x").unwrap_err()),
+        Error::from(RenderError::new("missing variable")),
+        Error::from(
+            Handlebars::new()
+                .register_template_string("t", "{{#if}}")
+                .unwrap_err(),
+        ),
+        Error::BadFormat("not a manifest".to_string()),
+        Error::BadImport("TWFoo.h does not exist".to_string()),
+        Error::UnsupportedTarget("Swift".to_string()),
+        Error::InvalidCommand,
+    ];
+
+    let messages = errors.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    for (idx, msg) in messages.iter().enumerate() {
+        assert!(!msg.is_empty());
+        assert!(!messages[..idx].contains(msg), "{msg}");
+    }
+
+    // Wrapped errors are exposed as the source.
+    assert!(errors[0].source().is_some());
+    assert!(errors[5].source().is_none());
+}
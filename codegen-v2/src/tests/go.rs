@@ -0,0 +1,27 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::with_current_year;
+use crate::codegen::go::{generate_go, generate_go_helpers};
+use crate::manifest::parse_str;
+
+#[test]
+fn go_bindings() {
+    const INPUT: &str = include_str!("samples/bindings.input.yaml");
+    const EXPECTED: &str = include_str!("samples/bindings.output.go");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let output = generate_go(&file_info).unwrap();
+    println!("{output}");
+    assert_eq!(output, with_current_year(EXPECTED));
+}
+
+#[test]
+fn go_helpers() {
+    let output = generate_go_helpers().unwrap();
+    assert!(output.contains("func goString(s unsafe.Pointer) string"));
+    assert!(output.contains("func goBytes(d unsafe.Pointer) []byte"));
+}
@@ -0,0 +1,25 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::with_current_year;
+use crate::codegen::json_marshal::generate_marshal;
+use crate::codegen::TargetLanguage;
+use crate::manifest::parse_str;
+use crate::Error;
+
+#[test]
+fn python_marshal() {
+    const INPUT: &str = include_str!("samples/struct_kinds.input.yaml");
+    const EXPECTED: &str = include_str!("samples/marshal.output.py");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let output = generate_marshal(&file_info, TargetLanguage::Python).unwrap();
+    println!("{output}");
+    assert_eq!(output, with_current_year(EXPECTED));
+
+    let err = generate_marshal(&file_info, TargetLanguage::Kotlin).unwrap_err();
+    assert!(matches!(err, Error::UnsupportedTarget(_)));
+}
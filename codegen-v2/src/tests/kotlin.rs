@@ -0,0 +1,32 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::with_current_year;
+use crate::codegen::kotlin::generate_kotlin;
+use crate::manifest::{parse_file, parse_str};
+
+#[test]
+fn kotlin_bindings() {
+    const INPUT: &str = include_str!("samples/bindings.input.yaml");
+    const EXPECTED: &str = include_str!("samples/bindings.output.kt");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let output = generate_kotlin(&file_info).unwrap();
+    println!("{output}");
+    assert_eq!(output, with_current_year(EXPECTED));
+}
+
+#[test]
+fn kotlin_static_function_keeps_params() {
+    let (_, file_info) = parse_file("manifest/TWSegwitAddress.yaml").unwrap();
+    let output = generate_kotlin(&file_info).unwrap();
+
+    // Only the implicit 'self' parameter of instance methods is skipped.
+    assert!(
+        output.contains("external fun equal(lhs: SegwitAddress, rhs: SegwitAddress): Boolean"),
+        "{output}"
+    );
+}
@@ -0,0 +1,1506 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::manifest::{
+    base_struct, build_manifest, check_receiver_consistency, classify_structs,
+    detect_import_cycles, exported_symbols, find_duplicate_types, normalize_comment,
+    pair_lifecycle, parse_dir, parse_dir_incremental, parse_dir_with_options, parse_file,
+    parse_iter, parse_str, resolve_imports, topo_order, undocumented_methods, validate_enum,
+    validate_manifest, write_manifests, ConstantInfo, EnumError, FieldInfo, FileInfo, ImportInfo,
+    Lifecycle, Manifest, ManifestCache, ManifestFormat, Ownership, ParseOptions, ReceiverMismatch,
+    StructKind, TypeInfo, TypeVariant, ValidationError, WriteOptions, CACHE_VERSION,
+    SCALAR_VARIANTS, VALUE_VARIANTS,
+};
+use crate::Error;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn array_roundtrip() {
+    const INPUT: &str = include_str!("samples/array.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let json = serde_json::to_string(&file_info).unwrap();
+    let file_info: FileInfo = serde_json::from_str(&json).unwrap();
+
+    let fields = &file_info.structs[0].fields;
+    assert_eq!(fields.len(), 2);
+
+    // Sized array, e.g. `uint8_t key[32]`.
+    let FieldInfo { name, ty, .. } = &fields[0];
+    assert_eq!(name, "key");
+    assert_eq!(
+        ty.variant,
+        TypeVariant::Array {
+            element: Box::new(TypeVariant::UInt8T),
+            len: Some(32),
+        }
+    );
+
+    // Unsized array, e.g. `uint8_t data[]`.
+    let FieldInfo { name, ty, .. } = &fields[1];
+    assert_eq!(name, "data");
+    assert_eq!(
+        ty.variant,
+        TypeVariant::Array {
+            element: Box::new(TypeVariant::UInt8T),
+            len: None,
+        }
+    );
+}
+
+#[test]
+fn pointer_array_param() {
+    const INPUT: &str = include_str!("samples/pointer_array.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let json = serde_json::to_string(&file_info).unwrap();
+    let file_info: FileInfo = serde_json::from_str(&json).unwrap();
+
+    // E.g. `const char* names[]`, an unsized array of C strings.
+    let param = &file_info.functions[0].params[0];
+    assert_eq!(param.name, "names");
+    assert_eq!(
+        param.ty.variant,
+        TypeVariant::Array {
+            element: Box::new(TypeVariant::CString),
+            len: None,
+        }
+    );
+    assert!(param.ty.is_constant);
+    assert_eq!(param.ty.pointer_depth, 0);
+}
+
+#[test]
+fn function_pointer_field() {
+    const INPUT: &str = include_str!("samples/function_pointer.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let json = serde_json::to_string(&file_info).unwrap();
+    let file_info: FileInfo = serde_json::from_str(&json).unwrap();
+
+    // E.g. `void (*callback)(const void*)`.
+    let FieldInfo { name, ty, .. } = &file_info.structs[0].fields[0];
+    assert_eq!(name, "callback");
+    assert!(ty.is_nullable);
+
+    let TypeVariant::FunctionPointer { ret, params } = &ty.variant else {
+        panic!("expected a function pointer, found: {:?}", ty.variant);
+    };
+    assert_eq!(ret.variant, TypeVariant::Void);
+    assert!(!ret.is_pointer());
+
+    assert_eq!(params.len(), 1);
+    assert_eq!(params[0].variant, TypeVariant::Void);
+    assert!(params[0].is_constant);
+    assert_eq!(params[0].pointer_depth, 1);
+}
+
+#[test]
+fn resolve_header_imports() {
+    const INPUT: &str = include_str!("samples/imports.input.yaml");
+    let include_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../include/TrustWalletCore");
+
+    let mut file_info = parse_str(INPUT).unwrap();
+    let resolved = resolve_imports(&include_dir, &file_info).unwrap();
+
+    // Relative segments are normalized before the lookup.
+    let expected = vec![
+        include_dir.join("TWBase.h").canonicalize().unwrap(),
+        include_dir.join("TWData.h").canonicalize().unwrap(),
+    ];
+    assert_eq!(resolved, expected);
+
+    // Imports must correspond to an existing header.
+    file_info.imports.push(ImportInfo {
+        path: vec!["TWDoesNotExist.h".to_string()],
+        is_system: false,
+    });
+    let err = resolve_imports(&include_dir, &file_info).unwrap_err();
+    assert!(matches!(err, Error::BadImport(_)));
+}
+
+#[test]
+fn resolve_backslash_imports() {
+    let include_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../include/TrustWalletCore");
+    let mut file_info = parse_str("name: TWFoo").unwrap();
+
+    let forward: ImportInfo = "#include \"../TrustWalletCore/TWData.h\"".parse().unwrap();
+    file_info.imports = vec![forward];
+    let expected = resolve_imports(&include_dir, &file_info).unwrap();
+
+    let backward: ImportInfo = "#include \"..\\TrustWalletCore\\TWData.h\""
+        .parse()
+        .unwrap();
+    file_info.imports = vec![backward];
+    assert_eq!(resolve_imports(&include_dir, &file_info).unwrap(), expected);
+
+    // Manifest files might contain unsplit Windows paths.
+    file_info.imports = vec![ImportInfo {
+        path: vec!["..\\TrustWalletCore\\TWData.h".to_string()],
+        is_system: false,
+    }];
+    assert_eq!(resolve_imports(&include_dir, &file_info).unwrap(), expected);
+}
+
+#[test]
+fn macro_constants() {
+    let int: ConstantInfo = "#define TW_X 42".parse().unwrap();
+    assert_eq!(int.name, "TW_X");
+    assert_eq!(int.value_type, TypeVariant::Int);
+    assert_eq!(int.value, "42");
+
+    let hex: ConstantInfo = "#  define TW_MASK 0x80000000U".parse().unwrap();
+    assert_eq!(hex.value, "0x80000000U");
+
+    let string: ConstantInfo = "#define TW_NAME \"Trust\"".parse().unwrap();
+    assert_eq!(string.value_type, TypeVariant::String);
+    assert_eq!(string.value, "Trust");
+
+    // Function-like macros and other expressions are skipped.
+    assert!("#define TW_MAX(a, b) ((a) > (b) ? (a) : (b))"
+        .parse::<ConstantInfo>()
+        .is_err());
+    assert!("#define TW_EXPORT_CLASS".parse::<ConstantInfo>().is_err());
+    assert!("#define TW_Y (1 << 2)".parse::<ConstantInfo>().is_err());
+    assert!("#defineTW_X 42".parse::<ConstantInfo>().is_err());
+
+    // Constants are part of the manifest files.
+    let file_info = parse_str(
+        "name: TWHDVersion\nconstants:\n- name: TW_HD_VERSION\n  value_type:\n    variant: int\n  value: '3'\n",
+    )
+    .unwrap();
+    assert_eq!(file_info.constants[0].name, "TW_HD_VERSION");
+    assert_eq!(file_info.constants[0].value, "3");
+}
+
+#[test]
+fn system_and_local_includes() {
+    let system: ImportInfo = "#include <stdint.h>".parse().unwrap();
+    assert_eq!(system.path, vec!["stdint.h"]);
+    assert!(system.is_system);
+
+    let local: ImportInfo = "#include \"TWData.h\"".parse().unwrap();
+    assert_eq!(local.path, vec!["TWData.h"]);
+    assert!(!local.is_system);
+
+    let nested: ImportInfo = "# include <TrustWalletCore/TWBase.h>".parse().unwrap();
+    assert_eq!(nested.path, vec!["TrustWalletCore", "TWBase.h"]);
+
+    // Windows separators are split, too.
+    let windows: ImportInfo = "#include <TrustWalletCore\\TWBase.h>".parse().unwrap();
+    assert_eq!(windows, nested);
+
+    assert!("#include TWData.h".parse::<ImportInfo>().is_err());
+    assert!("#import \"TWData.h\"".parse::<ImportInfo>().is_err());
+
+    // System includes are not part of the C headers.
+    let include_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../include/TrustWalletCore");
+    let mut file_info = parse_str("name: TWFoo").unwrap();
+    file_info.imports = vec![system, local];
+    let resolved = resolve_imports(&include_dir, &file_info).unwrap();
+    assert_eq!(
+        resolved,
+        vec![include_dir.join("TWData.h").canonicalize().unwrap()]
+    );
+}
+
+#[test]
+fn parse_dir_rejects_non_manifest_files() {
+    // The samples directory also contains the expected Swift outputs.
+    let samples_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/tests/samples");
+
+    let err = parse_dir(samples_dir).unwrap_err();
+    assert!(matches!(err, Error::BadFormat(_)));
+}
+
+#[test]
+fn deprecation_roundtrip() {
+    const INPUT: &str = include_str!("samples/deprecated.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let json = serde_json::to_string(&file_info).unwrap();
+    let file_info: FileInfo = serde_json::from_str(&json).unwrap();
+
+    assert!(file_info.structs[0].is_deprecated);
+    assert_eq!(file_info.structs[0].deprecation_note, None);
+
+    let (first, second) = (&file_info.functions[0], &file_info.functions[1]);
+    assert!(first.is_deprecated);
+    assert_eq!(
+        first.deprecation_note.as_deref(),
+        Some("Use \"secondFunction\" instead")
+    );
+    assert!(!second.is_deprecated);
+    assert_eq!(second.deprecation_note, None);
+}
+
+#[test]
+fn pointer_depth() {
+    const INPUT: &str = include_str!("samples/pointer_depth.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let params = &file_info.functions[0].params;
+
+    // E.g. `TWData* input`.
+    assert_eq!(params[0].ty.pointer_depth, 1);
+    assert!(params[0].ty.is_pointer());
+
+    // E.g. `TWData** result`.
+    assert_eq!(params[1].ty.pointer_depth, 2);
+    assert!(params[1].ty.is_pointer());
+
+    // Return value not a pointer.
+    assert_eq!(file_info.functions[0].return_type.pointer_depth, 0);
+    assert!(!file_info.functions[0].return_type.is_pointer());
+}
+
+#[test]
+fn resolve_typedef() {
+    const INPUT: &str = include_str!("samples/typedef.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+    assert_eq!(file_info.typedefs.len(), 2);
+    assert_eq!(file_info.typedefs[1].name, "TWStoredKeyEncryption");
+
+    let params = &file_info.functions[0].params;
+
+    // The alias is flattened to the underlying type.
+    let resolved = file_info.resolve_typedef(&params[0].ty);
+    assert_eq!(resolved.variant, TypeVariant::UInt64T);
+    assert_eq!(resolved.pointer_depth, 0);
+
+    // Other types are left untouched.
+    let resolved = file_info.resolve_typedef(&params[1].ty);
+    assert_eq!(resolved, params[1].ty);
+
+    // Self-referencing aliases, e.g. `typedef struct TWFoo TWFoo;`.
+    let ty = file_info.typedefs[0].underlying.clone();
+    assert_eq!(file_info.resolve_typedef(&ty), ty);
+}
+
+#[test]
+fn associated_type() {
+    const INPUT: &str = include_str!("samples/associated.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let functions = &file_info.functions;
+
+    assert_eq!(functions[0].associated_type.as_deref(), Some("TWMnemonic"));
+    // The longest matching prefix wins.
+    assert_eq!(
+        functions[1].associated_type.as_deref(),
+        Some("TWMnemonicWord")
+    );
+    // Free function without an owning type.
+    assert_eq!(functions[2].associated_type, None);
+}
+
+#[test]
+fn byte_types() {
+    const INPUT: &str = include_str!("samples/bytes.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let func = &file_info.functions[0];
+
+    let input = &func.params[0].ty;
+    assert_eq!(input.variant, TypeVariant::Data);
+    assert!(input.is_pointer());
+    assert!(input.is_nullable);
+
+    assert_eq!(func.return_type.variant, TypeVariant::Data);
+    assert!(!func.return_type.is_nullable);
+}
+
+#[test]
+fn combined_manifest_type_index() {
+    let manifest = build_manifest("manifest/").unwrap();
+
+    // `TWSegwitAddress` references `TWPublicKey`, which is defined in
+    // another file.
+    let segwit = manifest
+        .files
+        .iter()
+        .find(|file| file.name == "TWSegwitAddress")
+        .unwrap();
+    let reference = segwit
+        .structs
+        .iter()
+        .find(|s| s.name == "TWPublicKey")
+        .unwrap();
+    assert!(!reference.is_public);
+
+    let path = &manifest.type_index["TWPublicKey"];
+    assert!(path.ends_with("TWPublicKey.yaml"));
+
+    let defining = manifest.defining_file("TWPublicKey").unwrap();
+    assert_eq!(defining.name, "TWPublicKey");
+    assert!(defining
+        .structs
+        .iter()
+        .any(|s| s.name == "TWPublicKey" && s.is_public));
+
+    assert!(manifest.defining_file("TWUnknown").is_none());
+}
+
+#[test]
+fn block_comments() {
+    const INPUT: &str = include_str!("samples/comments.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+
+    assert_eq!(
+        file_info.functions[0].comments,
+        vec!["Validates the mnemonic.", "Returns true if it is valid."]
+    );
+    assert_eq!(
+        file_info.properties[0].comments,
+        vec!["Returns the first property."]
+    );
+}
+
+#[test]
+fn parallel_parse_dir_is_deterministic() {
+    let parallel = parse_dir("manifest/").unwrap();
+
+    // Parse the same files serially, sorted by name.
+    let mut file_paths = std::fs::read_dir("manifest/")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    file_paths.sort();
+
+    let mut serial = file_paths
+        .iter()
+        .map(|path| parse_file(path).unwrap().1)
+        .collect::<Vec<_>>();
+    serial.sort_by(|a, b| a.name.cmp(&b.name));
+
+    assert_eq!(
+        serde_json::to_value(&parallel).unwrap(),
+        serde_json::to_value(&serial).unwrap()
+    );
+    assert!(parallel.windows(2).all(|w| w[0].name <= w[1].name));
+}
+
+#[test]
+fn validate_unknown_types() {
+    assert!(validate_manifest(&build_manifest("manifest/").unwrap()).is_ok());
+
+    let file_info = parse_str(include_str!("samples/unknown_type.input.yaml")).unwrap();
+    let manifest = Manifest {
+        type_index: [("TWKnown".to_string(), PathBuf::from("TWKnown.yaml"))].into(),
+        files: vec![file_info],
+        file_paths: Default::default(),
+    };
+
+    let errors = validate_manifest(&manifest).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![ValidationError::UnknownType {
+            name: "TWUnknwon".to_string(),
+            referenced_in: "TWKnown".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn enum_and_struct_references() {
+    let manifest = build_manifest("manifest/").unwrap();
+
+    // E.g. `enum TWCoinType coin`.
+    let any_address = manifest.defining_file("TWAnyAddress").unwrap();
+    let coin = any_address.inits[0]
+        .params
+        .iter()
+        .find(|param| param.name == "coin")
+        .unwrap();
+    assert_eq!(coin.ty.variant, TypeVariant::Enum("TWCoinType".to_string()));
+
+    // References agree with the kind of the declared type.
+    for file in &manifest.files {
+        for variant in file.types().map(|ty| &ty.variant) {
+            let (name, is_enum) = match variant {
+                TypeVariant::Struct(name) => (name, false),
+                TypeVariant::Enum(name) => (name, true),
+                _ => continue,
+            };
+            let Some(defining) = manifest.defining_file(name) else {
+                continue;
+            };
+
+            let declared_enum = defining.enums.iter().any(|e| &e.name == name);
+            assert_eq!(declared_enum, is_enum, "{name} in {}", file.name);
+        }
+    }
+}
+
+#[test]
+fn duplicate_types() {
+    assert!(find_duplicate_types(&build_manifest("manifest/").unwrap()).is_empty());
+
+    let first = parse_str(include_str!("samples/unknown_type.input.yaml")).unwrap();
+    let mut second = first.clone();
+    second.name = "TWKnownCopy".to_string();
+    // An enum sharing the name of the struct.
+    let third = parse_str(
+        "name: TWKnownEnum
+enums:
+- name: TWKnown
+  is_public: true
+  value_type:
+    variant: u_int32_t
+",
+    )
+    .unwrap();
+
+    let manifest = Manifest {
+        file_paths: [&first, &second, &third]
+            .iter()
+            .map(|f| (f.name.clone(), PathBuf::from(format!("{}.yaml", f.name))))
+            .collect(),
+        files: vec![first, second, third],
+        type_index: Default::default(),
+    };
+
+    assert_eq!(
+        find_duplicate_types(&manifest),
+        vec![(
+            "TWKnown".to_string(),
+            vec![
+                PathBuf::from("TWKnown.yaml"),
+                PathBuf::from("TWKnownCopy.yaml"),
+                PathBuf::from("TWKnownEnum.yaml"),
+            ]
+        )]
+    );
+}
+
+#[test]
+fn unresolved_types() {
+    // Enums of other files are not declared by the referencing file.
+    let (_, file_info) = parse_file("manifest/TWAES.yaml").unwrap();
+    assert_eq!(
+        file_info.unresolved_types(&[]),
+        vec!["TWAESPaddingMode".to_string()]
+    );
+
+    let file_info = parse_str(include_str!("samples/unknown_type.input.yaml")).unwrap();
+    assert_eq!(
+        file_info.unresolved_types(&[]),
+        vec!["TWUnknwon".to_string()]
+    );
+    assert!(file_info.unresolved_types(&["TWUnknwon"]).is_empty());
+}
+
+#[test]
+fn enum_value_expressions() {
+    const INPUT: &str = include_str!("samples/enum_values.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let values = file_info.enums[0]
+        .variants
+        .iter()
+        .map(|variant| variant.value.as_str())
+        .collect::<Vec<_>>();
+
+    // Plain literals are kept as is.
+    assert_eq!(values, vec!["0xFF", "256", "2", "0x04", "6", "22"]);
+}
+
+#[test]
+fn enum_value_invalid() {
+    const INPUT: &str = include_str!("samples/enum_values.input.yaml");
+
+    // Invalid values are kept as is, and reported by the validation. Variants
+    // referring to them are invalid, too.
+    let input = INPUT.replace("flagA | TWFlagsFlagB", "flagA | unknown");
+    let file_info = parse_str(&input).unwrap();
+    assert_eq!(file_info.enums[0].variants[4].value, "flagA | unknown");
+
+    let invalid = |variant: &str, value: &str| EnumError::InvalidValue {
+        variant: variant.to_string(),
+        value: value.to_string(),
+    };
+    assert_eq!(
+        validate_enum(&file_info.enums[0]),
+        Err(vec![
+            invalid("combined", "flagA | unknown"),
+            invalid("nested", "(1 << 4) | combined"),
+        ])
+    );
+}
+
+#[test]
+fn enum_value_negative() {
+    let file_info = parse_str(include_str!("samples/enum_negative.input.yaml")).unwrap();
+    let enm = &file_info.enums[0];
+
+    let values = enm
+        .variants
+        .iter()
+        .map(|variant| variant.value.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(values, vec!["-1", "-128", "lowest - 1", "-129"]);
+
+    // Only unary minus is supported.
+    assert_eq!(
+        validate_enum(enm),
+        Err(vec![
+            EnumError::InvalidValue {
+                variant: "below".to_string(),
+                value: "lowest - 1".to_string(),
+            },
+            EnumError::OutOfRange {
+                variant: "tooLow".to_string(),
+                value: -129,
+            },
+        ])
+    );
+}
+
+#[test]
+fn combined_manifest_is_deterministic() {
+    let first = serde_json::to_string(&build_manifest("manifest/").unwrap()).unwrap();
+    let second = serde_json::to_string(&build_manifest("manifest/").unwrap()).unwrap();
+    assert_eq!(first, second);
+
+    let manifest = build_manifest("manifest/").unwrap();
+    for file in &manifest.files {
+        assert!(file.functions.windows(2).all(|w| w[0].name <= w[1].name));
+        assert!(file.properties.windows(2).all(|w| w[0].name <= w[1].name));
+    }
+
+    // Enum variants keep their declared order.
+    let coin_type = manifest.defining_file("TWCoinType").unwrap();
+    let variants = &coin_type.enums[0].variants;
+    assert!(variants.windows(2).any(|w| w[0].name > w[1].name));
+}
+
+#[test]
+fn write_manifests_roundtrip() {
+    let files = parse_dir("manifest/").unwrap();
+    let out_dir = std::env::temp_dir().join(format!("codegen-v2-manifests-{}", std::process::id()));
+
+    for format in [ManifestFormat::Json, ManifestFormat::Yaml] {
+        let options = WriteOptions {
+            format,
+            ..Default::default()
+        };
+        let written = write_manifests(&files, &out_dir, &options).unwrap();
+        assert_eq!(written.len(), files.len());
+
+        for ((path, _), expected) in written.iter().zip(&files) {
+            assert_eq!(path.extension().unwrap(), format.extension());
+
+            let content = std::fs::read_to_string(path).unwrap();
+            let file_info: FileInfo = match format {
+                ManifestFormat::Json => serde_json::from_str(&content).unwrap(),
+                ManifestFormat::Yaml => parse_str(&content).unwrap(),
+            };
+            assert_eq!(&file_info, expected);
+        }
+    }
+
+    std::fs::remove_dir_all(out_dir).unwrap();
+}
+
+#[test]
+fn write_compact_json() {
+    let files = parse_dir("manifest/").unwrap();
+    let out_dir = std::env::temp_dir().join(format!("codegen-v2-compact-{}", std::process::id()));
+
+    let mut sizes = vec![];
+    for pretty in [true, false] {
+        let options = WriteOptions {
+            format: ManifestFormat::Json,
+            pretty,
+            ..Default::default()
+        };
+        let written = write_manifests(&files, out_dir.join(pretty.to_string()), &options).unwrap();
+
+        let mut size = 0;
+        for ((path, len), expected) in written.iter().zip(&files) {
+            let content = std::fs::read_to_string(path).unwrap();
+            let file_info: FileInfo = serde_json::from_str(&content).unwrap();
+            assert_eq!(&file_info, expected);
+            assert_eq!(content.len(), *len);
+            size += len;
+        }
+        sizes.push(size);
+    }
+
+    // Pretty output is the default.
+    assert!(WriteOptions::default().pretty);
+    assert!(sizes[1] < sizes[0]);
+
+    std::fs::remove_dir_all(out_dir).unwrap();
+}
+
+#[test]
+fn write_manifests_dry_run() {
+    let files = parse_dir("manifest/").unwrap();
+    let out_dir = std::env::temp_dir().join(format!("codegen-v2-dry-run-{}", std::process::id()));
+
+    let options = WriteOptions {
+        dry_run: true,
+        ..Default::default()
+    };
+    let planned = write_manifests(&files, out_dir.join("dry"), &options).unwrap();
+    assert!(!out_dir.exists());
+
+    // The same files are reported as when actually writing them.
+    let written = write_manifests(&files, out_dir.join("dry"), &WriteOptions::default()).unwrap();
+    assert_eq!(planned, written);
+    for (path, len) in &written {
+        assert_eq!(std::fs::metadata(path).unwrap().len() as usize, *len);
+    }
+
+    std::fs::remove_dir_all(out_dir).unwrap();
+}
+
+#[test]
+fn detect_circular_imports() {
+    let dir = std::env::temp_dir().join(format!("codegen-v2-cycles-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for header in ["TWFirst.h", "TWSecond.h", "TWThird.h"] {
+        std::fs::write(dir.join(header), "").unwrap();
+    }
+
+    let file = |name: &str, imports: &[&str]| FileInfo {
+        imports: imports
+            .iter()
+            .map(|import| ImportInfo {
+                path: vec![import.to_string()],
+                is_system: false,
+            })
+            .collect(),
+        ..parse_str(&format!("name: {name}")).unwrap()
+    };
+
+    // `TWFirst.h` and `TWSecond.h` include each other, `TWThird.h` only
+    // includes `TWFirst.h`.
+    let files = vec![
+        file("TWFirst", &["TWSecond.h"]),
+        file("TWSecond", &["TWFirst.h"]),
+        file("TWThird", &["TWFirst.h"]),
+    ];
+
+    let cycles = detect_import_cycles(&dir, &files).unwrap();
+    let expected = vec![vec![
+        dir.join("TWFirst.h").canonicalize().unwrap(),
+        dir.join("TWSecond.h").canonicalize().unwrap(),
+    ]];
+    assert_eq!(cycles, expected);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn doxygen_comment_sections() {
+    let raw = "\\brief Returns the address.\n\
+        For the given coin.\n\
+        @param coin The coin type\n\
+        @param[in] key The public key,\n\
+        which must be compressed.\n\
+        @see TWAnyAddress\n\
+        \\return The address string.";
+
+    let doc = normalize_comment(raw);
+    assert_eq!(
+        doc.brief.as_deref(),
+        Some("Returns the address. For the given coin.")
+    );
+    assert_eq!(doc.params.len(), 2);
+    assert_eq!(doc.params["coin"], "The coin type");
+    assert_eq!(
+        doc.params["key"],
+        "The public key, which must be compressed."
+    );
+    assert_eq!(doc.returns.as_deref(), Some("The address string."));
+
+    // Untagged comments are the brief.
+    let doc = normalize_comment("Validates the mnemonic.");
+    assert_eq!(doc.brief.as_deref(), Some("Validates the mnemonic."));
+    assert!(doc.params.is_empty());
+    assert_eq!(doc.returns, None);
+}
+
+#[test]
+fn parse_single_file() {
+    let files = parse_dir("manifest/").unwrap();
+
+    let (path, file_info) = parse_file("manifest/TWMnemonic.yaml").unwrap();
+    assert_eq!(path, Path::new("manifest/TWMnemonic.yaml"));
+
+    let expected = files.iter().find(|file| file.name == "TWMnemonic").unwrap();
+    assert_eq!(&file_info, expected);
+
+    // The lazy iterator yields the same files, in directory order.
+    let mut streamed = parse_iter("manifest/")
+        .unwrap()
+        .collect::<crate::Result<Vec<_>>>()
+        .unwrap();
+    streamed.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(streamed, files);
+}
+
+#[test]
+fn char_signedness() {
+    let variant =
+        |name: &str| -> TypeVariant { serde_yaml::from_str(&format!("variant: {name}")).unwrap() };
+
+    // Plain `char` remains a character, e.g. for `const char*` strings.
+    assert_eq!(variant("char"), TypeVariant::Char);
+    assert_eq!(variant("signed_char"), TypeVariant::Int8T);
+    assert_eq!(variant("unsigned_char"), TypeVariant::UInt8T);
+
+    // The canonical names are used when serializing.
+    let yaml = serde_yaml::to_string(&TypeVariant::UInt8T).unwrap();
+    assert_eq!(yaml.trim(), "variant: u_int8_t");
+}
+
+#[test]
+fn struct_field_order() {
+    const INPUT: &str = include_str!("samples/field_order.input.yaml");
+
+    let mut file_info = parse_str(INPUT).unwrap();
+    file_info.sort_items();
+
+    // Declaration order is kept, even when sorting the items.
+    let expected = vec!["c", "a", "b"];
+    let names = |file_info: &FileInfo| -> Vec<String> {
+        file_info.structs[0]
+            .fields
+            .iter()
+            .map(|field| field.name.clone())
+            .collect()
+    };
+    assert_eq!(names(&file_info), expected);
+
+    let json = serde_json::to_string(&file_info).unwrap();
+    let file_info: FileInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(names(&file_info), expected);
+}
+
+#[test]
+fn pointee_and_pointer_const() {
+    const INPUT: &str = include_str!("samples/const_pointer.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let consts = file_info.functions[0]
+        .params
+        .iter()
+        .map(|param| (param.ty.is_constant, param.ty.is_pointer_const))
+        .collect::<Vec<_>>();
+
+    assert_eq!(consts, vec![(true, false), (false, true), (true, true)]);
+
+    // Non-const pointers omit the flag.
+    let yaml = serde_yaml::to_string(&file_info).unwrap();
+    assert_eq!(yaml.matches("is_pointer_const: true").count(), 2);
+    assert!(!yaml.contains("is_pointer_const: false"));
+}
+
+#[test]
+fn pointer_sized_integers() {
+    let variant =
+        |name: &str| -> TypeVariant { serde_yaml::from_str(&format!("variant: {name}")).unwrap() };
+
+    assert_eq!(variant("size_t"), TypeVariant::SizeT);
+    assert_eq!(variant("uintptr_t"), TypeVariant::SizeT);
+    assert_eq!(variant("intptr_t"), TypeVariant::IntPtr);
+    assert_eq!(variant("ssize_t"), TypeVariant::IntPtr);
+
+    // `bool` and the `_Bool` keyword are the same type.
+    assert_eq!(variant("bool"), TypeVariant::Bool);
+    assert_eq!(variant("_Bool"), TypeVariant::Bool);
+
+    let yaml = serde_yaml::to_string(&TypeVariant::IntPtr).unwrap();
+    assert_eq!(yaml.trim(), "variant: intptr_t");
+}
+
+#[test]
+fn incremental_parse_reuses_unchanged_files() {
+    let dir = std::env::temp_dir().join(format!("codegen-v2-incremental-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for name in ["TWAES.yaml", "TWBase32.yaml"] {
+        std::fs::copy(Path::new("manifest/").join(name), dir.join(name)).unwrap();
+    }
+
+    let mut cache = ManifestCache::default();
+    let files = parse_dir_incremental(&dir, &mut cache).unwrap();
+    assert_eq!(files, parse_dir(&dir).unwrap());
+    assert_eq!(cache.entries.len(), 2);
+
+    // The cache keys are the stable content hashes of the files.
+    for (hash, info) in cache.entries.values() {
+        assert_eq!(Some(format!("{hash:016x}")), info.generated_from_hash);
+    }
+
+    // Tamper with the cached entries, so it shows which ones are reused.
+    for (_, info) in cache.entries.values_mut() {
+        info.name = format!("{}Cached", info.name);
+    }
+
+    // Modify one file, which invalidates its entry only.
+    let modified = dir.join("TWBase32.yaml");
+    let content = std::fs::read_to_string(&modified).unwrap();
+    std::fs::write(&modified, format!("{content}\n# modified\n")).unwrap();
+
+    let cache_path = dir.join("cache.json");
+    cache.save(&cache_path).unwrap();
+    let mut cache = ManifestCache::load(&cache_path).unwrap();
+    std::fs::remove_file(&cache_path).unwrap();
+
+    let files = parse_dir_incremental(&dir, &mut cache).unwrap();
+    let names = files.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["TWAESCached", "TWBase32"]);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn load_missing_cache() {
+    let cache = ManifestCache::load("does/not/exist.json").unwrap();
+    assert!(cache.entries.is_empty());
+}
+
+#[test]
+fn load_stale_cache() {
+    let dir = std::env::temp_dir().join(format!("codegen-v2-stale-cache-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let cache_path = dir.join("cache.json");
+
+    let (path, info) = parse_file("manifest/TWAES.yaml").unwrap();
+    let mut cache = ManifestCache::default();
+    cache.entries.insert(path, (0, info));
+    cache.save(&cache_path).unwrap();
+    assert_eq!(ManifestCache::load(&cache_path).unwrap().entries.len(), 1);
+
+    // Caches of other versions are discarded.
+    cache.version = CACHE_VERSION + 1;
+    cache.save(&cache_path).unwrap();
+    let loaded = ManifestCache::load(&cache_path).unwrap();
+    assert!(loaded.entries.is_empty());
+    assert_eq!(loaded.version, CACHE_VERSION);
+
+    // As are caches which cannot be deserialized, e.g. of older versions
+    // without a version key.
+    std::fs::write(&cache_path, r#"{"entries": {"TWAES.yaml": [0, {}]}}"#).unwrap();
+    assert!(ManifestCache::load(&cache_path).unwrap().entries.is_empty());
+    std::fs::write(&cache_path, "not json").unwrap();
+    assert!(ManifestCache::load(&cache_path).unwrap().entries.is_empty());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn unnamed_params() {
+    let file_info = parse_str(include_str!("samples/unnamed_params.input.yaml")).unwrap();
+    let params = &file_info.functions[0].params;
+
+    let names = params.iter().map(|p| p.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["arg0", "arg1", "count"]);
+    assert_eq!(params[0].ty.variant, TypeVariant::Int);
+    assert_eq!(params[1].ty.variant, TypeVariant::Char);
+    assert_eq!(params[1].ty.pointer_depth, 1);
+}
+
+#[test]
+fn parse_error_location() {
+    let input = "name: TWFoo
+structs:
+- name: TWFoo
+  is_public: maybe
+  is_class: false
+";
+
+    let Err(Error::YamlError(err)) = parse_str(input) else {
+        panic!("expected a YAML error");
+    };
+
+    // Points at the offending `is_public` value.
+    let location = err.location().unwrap();
+    assert_eq!((location.line(), location.column()), (4, 14));
+}
+
+#[test]
+fn lifecycle_pairs() {
+    let (_, file_info) = parse_file("manifest/TWPrivateKey.yaml").unwrap();
+
+    assert_eq!(
+        pair_lifecycle(&file_info),
+        vec![Lifecycle {
+            ty: "TWPrivateKey".to_string(),
+            constructors: vec![
+                "TWPrivateKeyCreate".to_string(),
+                "TWPrivateKeyCreateWithData".to_string(),
+                "TWPrivateKeyCreateCopy".to_string(),
+            ],
+            destructor: Some("TWPrivateKeyDelete".to_string()),
+        }]
+    );
+
+    // Non-class structs have neither.
+    let (_, file_info) = parse_file("manifest/TWMnemonic.yaml").unwrap();
+    assert!(pair_lifecycle(&file_info).is_empty());
+}
+
+#[test]
+fn parse_dir_include_filter() {
+    let options = ParseOptions {
+        include_filter: Some(vec!["TWMnemonic*".to_string()]),
+        ..Default::default()
+    };
+    let files = parse_dir_with_options("manifest/", &options).unwrap();
+    let names = files.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["TWMnemonic"]);
+
+    let options = ParseOptions {
+        include_filter: Some(vec![
+            "TWAny?ddress.yaml".to_string(),
+            "*Mnemonic*".to_string(),
+        ]),
+        ..Default::default()
+    };
+    let files = parse_dir_with_options("manifest/", &options).unwrap();
+    let names = files.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["TWAnyAddress", "TWMnemonic"]);
+
+    // An empty filter includes all files.
+    let options = ParseOptions {
+        include_filter: Some(vec![]),
+        ..Default::default()
+    };
+    let all = parse_dir("manifest/").unwrap();
+    assert_eq!(parse_dir_with_options("manifest/", &options).unwrap(), all);
+}
+
+#[test]
+fn strict_unknown_types() {
+    let dir = std::env::temp_dir().join(format!("codegen-v2-strict-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("TWUnknown.yaml"),
+        include_str!("samples/unknown_variant.input.yaml"),
+    )
+    .unwrap();
+    std::fs::copy("manifest/TWMnemonic.yaml", dir.join("TWMnemonic.yaml")).unwrap();
+
+    // Lenient by default.
+    assert_eq!(parse_dir(&dir).unwrap().len(), 2);
+
+    let mut options = ParseOptions {
+        strict: true,
+        ..Default::default()
+    };
+    let err = parse_dir_with_options(&dir, &options).unwrap_err();
+    let Error::BadType(msg) = err else {
+        panic!("expected a bad type error, found: {err:?}");
+    };
+    assert!(msg.contains("Whatever"), "{msg}");
+    assert!(!msg.contains("TWMnemonic"), "{msg}");
+
+    options.allowed_unknown_types = vec!["Whatever".to_string()];
+    assert_eq!(parse_dir_with_options(&dir, &options).unwrap().len(), 2);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn unknown_type_variant() {
+    const INPUT: &str = include_str!("samples/unknown_variant.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let fields = &file_info.structs[0].fields;
+    assert_eq!(fields[0].ty.variant, TypeVariant::UInt32T);
+    assert_eq!(
+        fields[1].ty.variant,
+        TypeVariant::Unknown("Whatever".to_string())
+    );
+    assert_eq!(
+        fields[2].ty.variant,
+        TypeVariant::Array {
+            element: Box::new(TypeVariant::Unknown("Whatever".to_string())),
+            len: Some(4),
+        }
+    );
+
+    // Reported once, in order of occurrence.
+    assert_eq!(file_info.unknown_types(), vec!["Whatever".to_string()]);
+
+    // The raw keyword is written back as is.
+    let yaml = serde_yaml::to_string(&file_info).unwrap();
+    assert_eq!(parse_str(&yaml).unwrap(), file_info);
+    let json = serde_json::to_string(&file_info).unwrap();
+    assert_eq!(serde_json::from_str::<FileInfo>(&json).unwrap(), file_info);
+
+    // Malformed values of known variants are still rejected.
+    let err = serde_yaml::from_str::<TypeVariant>("variant: array\nvalue: 4");
+    assert!(err.is_err());
+    let err = serde_yaml::from_str::<TypeVariant>("variant: struct");
+    assert!(err.is_err());
+}
+
+#[test]
+fn type_variant_tags() {
+    // Every known tag is classified as such, rather than as `Unknown`.
+    let tags = SCALAR_VARIANTS.iter().chain(VALUE_VARIANTS);
+    for tag in tags.clone() {
+        let variant = serde_yaml::from_str::<TypeVariant>(&format!("variant: {tag}"));
+        assert!(!matches!(variant, Ok(TypeVariant::Unknown(_))), "{tag}");
+    }
+
+    // And every variant is written with a known tag.
+    let ty = TypeInfo {
+        variant: TypeVariant::Void,
+        is_constant: false,
+        is_nullable: false,
+        pointer_depth: 0,
+        is_pointer_const: false,
+    };
+    let variants = [
+        TypeVariant::Void,
+        TypeVariant::Bool,
+        TypeVariant::Char,
+        TypeVariant::ShortInt,
+        TypeVariant::Int,
+        TypeVariant::UnsignedInt,
+        TypeVariant::LongInt,
+        TypeVariant::UnsignedLongInt,
+        TypeVariant::Float,
+        TypeVariant::Double,
+        TypeVariant::SizeT,
+        TypeVariant::IntPtr,
+        TypeVariant::Int8T,
+        TypeVariant::Int16T,
+        TypeVariant::Int32T,
+        TypeVariant::Int64T,
+        TypeVariant::UInt8T,
+        TypeVariant::UInt16T,
+        TypeVariant::UInt32T,
+        TypeVariant::UInt64T,
+        TypeVariant::Struct("TWFoo".to_string()),
+        TypeVariant::Enum("TWFoo".to_string()),
+        TypeVariant::Data,
+        TypeVariant::String,
+        TypeVariant::CString,
+        TypeVariant::Array {
+            element: Box::new(TypeVariant::UInt8T),
+            len: None,
+        },
+        TypeVariant::FunctionPointer {
+            ret: Box::new(ty.clone()),
+            params: vec![ty],
+        },
+    ];
+    for variant in variants {
+        let value = serde_yaml::to_value(&variant).unwrap();
+        let tag = value["variant"].as_str().unwrap();
+        assert!(tags.clone().any(|known| *known == tag), "{tag}");
+        assert_eq!(
+            serde_yaml::from_value::<TypeVariant>(value).unwrap(),
+            variant
+        );
+    }
+}
+
+#[test]
+fn topological_import_order() {
+    let dir = std::env::temp_dir().join(format!("codegen-v2-topo-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for header in ["TWA.h", "TWB.h", "TWC.h"] {
+        std::fs::write(dir.join(header), "").unwrap();
+    }
+
+    let file = |name: &str, imports: &[&str]| FileInfo {
+        imports: imports
+            .iter()
+            .map(|import| ImportInfo {
+                path: vec![import.to_string()],
+                is_system: false,
+            })
+            .collect(),
+        ..parse_str(&format!("name: {name}")).unwrap()
+    };
+
+    // `TWA.h` includes `TWB.h`, which includes `TWC.h`.
+    let mut files = vec![
+        file("TWA", &["TWB.h"]),
+        file("TWB", &["TWC.h"]),
+        file("TWC", &[]),
+    ];
+
+    let header = |name: &str| dir.join(name).canonicalize().unwrap();
+    let order = topo_order(&dir, &files).unwrap();
+    assert_eq!(
+        order,
+        vec![header("TWC.h"), header("TWB.h"), header("TWA.h")]
+    );
+
+    // Closing the chain into a cycle prevents an ordering.
+    files[2] = file("TWC", &["TWA.h"]);
+    let err = topo_order(&dir, &files).unwrap_err();
+    assert!(matches!(err, Error::BadImport(_)));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn bitfield_widths() {
+    const INPUT: &str = include_str!("samples/bitfield.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let widths = file_info.structs[0]
+        .fields
+        .iter()
+        .map(|field| (field.name.as_str(), field.bit_width))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        widths,
+        vec![("flag", Some(1)), ("kind", Some(7)), ("id", None)]
+    );
+
+    // Plain fields omit the width.
+    let yaml = serde_yaml::to_string(&file_info).unwrap();
+    assert_eq!(yaml.matches("bit_width").count(), 2);
+    assert_eq!(parse_str(&yaml).unwrap(), file_info);
+}
+
+#[test]
+fn field_comments() {
+    const INPUT: &str = include_str!("samples/field_comments.input.yaml");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let comments = file_info.structs[0]
+        .fields
+        .iter()
+        .map(|field| field.comments.clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        comments,
+        vec![
+            vec!["The horizontal coordinate.".to_string()],
+            vec![
+                "The vertical coordinate.".to_string(),
+                "Grows downwards.".to_string()
+            ],
+            vec![],
+        ]
+    );
+}
+
+#[test]
+fn void_and_void_pointer_returns() {
+    let file_info = parse_str(include_str!("samples/void_return.input.yaml")).unwrap();
+
+    // `void TWFooReset(struct TWFoo* foo)` has no value.
+    let ty = &file_info.functions[0].return_type;
+    assert_eq!(ty.variant, TypeVariant::Void);
+    assert!(!ty.is_pointer());
+    assert!(ty.is_unit());
+
+    // `void* TWFooHandle(struct TWFoo* foo)` returns an opaque pointer.
+    let ty = &file_info.functions[1].return_type;
+    assert_eq!(ty.variant, TypeVariant::Void);
+    assert!(ty.is_pointer());
+    assert!(!ty.is_unit());
+}
+
+#[test]
+fn multi_word_type_specifiers() {
+    let variant =
+        |name: &str| -> TypeVariant { serde_yaml::from_str(&format!("variant: {name}")).unwrap() };
+
+    assert_eq!(variant("long_long"), TypeVariant::Int64T);
+    assert_eq!(variant("unsigned_long_long"), TypeVariant::UInt64T);
+    assert_eq!(variant("long"), TypeVariant::LongInt);
+    assert_eq!(variant("unsigned_long"), TypeVariant::UnsignedLongInt);
+    assert_eq!(variant("unsigned_int"), TypeVariant::UnsignedInt);
+    assert_eq!(variant("unsigned"), TypeVariant::UnsignedInt);
+    assert_eq!(variant("short"), TypeVariant::ShortInt);
+
+    let yaml = serde_yaml::to_string(&TypeVariant::UnsignedLongInt).unwrap();
+    assert_eq!(yaml.trim(), "variant: unsigned_long_int");
+}
+
+#[test]
+fn enum_variant_comments() {
+    let file_info = parse_str(include_str!("samples/enum_comments.input.yaml")).unwrap();
+    let comments = file_info.enums[0]
+        .variants
+        .iter()
+        .map(|variant| variant.comments.clone())
+        .collect::<Vec<_>>();
+
+    // E.g. `TWFooKindFoo = 1, // the foo`.
+    assert_eq!(
+        comments,
+        vec![
+            vec!["the foo".to_string()],
+            vec!["The bar.".to_string(), "deprecated, use foo".to_string()],
+            vec![],
+        ]
+    );
+}
+
+#[test]
+fn validate_enum_values() {
+    let file_info = parse_str(include_str!("samples/enum_duplicates.input.yaml")).unwrap();
+
+    let errors = validate_enum(&file_info.enums[0]).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            EnumError::OutOfRange {
+                variant: "baz".to_string(),
+                value: 256,
+            },
+            EnumError::DuplicateValue {
+                value: 0,
+                variants: vec!["foo".to_string(), "bar".to_string()],
+            },
+        ]
+    );
+
+    // The enums of the manifest are consistent.
+    for file_info in parse_dir("manifest/").unwrap() {
+        for enm in &file_info.enums {
+            assert_eq!(validate_enum(enm), Ok(()), "{}", enm.name);
+        }
+    }
+}
+
+#[test]
+fn receiver_mismatches() {
+    let file_info = parse_str(include_str!("samples/receiver_mismatch.input.yaml")).unwrap();
+
+    assert_eq!(
+        check_receiver_consistency(&file_info),
+        vec![ReceiverMismatch {
+            function: "TWFooVerify".to_string(),
+            expected: "TWFoo".to_string(),
+            found: Some(TypeVariant::Struct("TWBar".to_string())),
+        }]
+    );
+}
+
+#[test]
+fn opaque_and_value_structs() {
+    let manifest = Manifest {
+        files: vec![parse_str(include_str!("samples/struct_kinds.input.yaml")).unwrap()],
+        type_index: Default::default(),
+        file_paths: Default::default(),
+    };
+
+    let kinds = classify_structs(&manifest);
+    assert_eq!(kinds.len(), 2);
+    assert_eq!(kinds["TWHandle"], StructKind::Opaque);
+    assert_eq!(kinds["TWPoint"], StructKind::Value);
+
+    // Most wallet-core types are opaque.
+    let kinds = classify_structs(&build_manifest("manifest/").unwrap());
+    assert_eq!(kinds["TWPrivateKey"], StructKind::Opaque);
+}
+
+#[test]
+fn function_signature_hash() {
+    let file_info = parse_str(include_str!("samples/bindings.input.yaml")).unwrap();
+    let sign = &file_info.functions[0];
+
+    // Parameter names and comments are not part of the signature.
+    let mut renamed = sign.clone();
+    renamed.params[1].name = "message".to_string();
+    renamed.comments = vec!["Signs the message.".to_string()];
+    assert_eq!(renamed.signature_hash(), sign.signature_hash());
+
+    let mut retyped = sign.clone();
+    retyped.params[1].ty.variant = TypeVariant::String;
+    assert_ne!(retyped.signature_hash(), sign.signature_hash());
+
+    let mut returns = sign.clone();
+    returns.return_type.is_nullable = false;
+    assert_ne!(returns.signature_hash(), sign.signature_hash());
+
+    // The hash does not depend on the process, e.g. on random hasher keys.
+    assert_eq!(sign.signature_hash(), 0x476e_250b_afcb_7d34);
+}
+
+#[test]
+fn source_provenance() {
+    let dir = std::env::temp_dir().join(format!("codegen-v2-provenance-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let enum_input = include_str!("samples/enum.input.yaml");
+    for (name, content) in [
+        ("first.yaml", enum_input),
+        ("second.yaml", enum_input),
+        ("third.yaml", include_str!("samples/class.input.yaml")),
+    ] {
+        std::fs::write(dir.join(name), content).unwrap();
+    }
+
+    let (_, first) = parse_file(dir.join("first.yaml")).unwrap();
+    let (_, second) = parse_file(dir.join("second.yaml")).unwrap();
+    let (_, third) = parse_file(dir.join("third.yaml")).unwrap();
+
+    assert_eq!(first.source_path, Some(dir.join("first.yaml")));
+    assert!(first.generated_from_hash.is_some());
+    assert_eq!(first.generated_from_hash, second.generated_from_hash);
+    assert_ne!(first.generated_from_hash, third.generated_from_hash);
+
+    // Parsed strings have no source.
+    let info = parse_str(enum_input).unwrap();
+    assert_eq!(info.source_path, None);
+    assert_eq!(info.generated_from_hash, None);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn exported_symbol_list() {
+    let (_, mnemonic) = parse_file("manifest/TWMnemonic.yaml").unwrap();
+    let (_, account) = parse_file("manifest/TWAccount.yaml").unwrap();
+    let mut manifest = Manifest {
+        files: vec![mnemonic.clone(), account],
+        ..Default::default()
+    };
+
+    let symbols = exported_symbols(&manifest);
+    assert_eq!(
+        symbols,
+        [
+            "TWAccountAddress",
+            "TWAccountCoin",
+            "TWAccountCreate",
+            "TWAccountDelete",
+            "TWAccountDerivation",
+            "TWAccountDerivationPath",
+            "TWAccountExtendedPublicKey",
+            "TWAccountPublicKey",
+            "TWMnemonicIsValid",
+            "TWMnemonicIsValidWord",
+            "TWMnemonicSuggest",
+        ]
+    );
+
+    // Symbols are listed once, non-public functions not at all.
+    let mut private = mnemonic.clone();
+    private.functions[0].name = "TWMnemonicInternal".to_string();
+    private.functions[0].is_public = false;
+    manifest.files.extend([mnemonic, private]);
+    assert_eq!(exported_symbols(&manifest), symbols);
+}
+
+#[test]
+fn return_value_ownership() {
+    let (_, mut file_info) = parse_file("manifest/TWMnemonic.yaml").unwrap();
+
+    // `TWString* TWMnemonicSuggest(...)` hands the string over to the caller.
+    let suggest = &file_info.functions[2];
+    assert_eq!(suggest.name, "TWMnemonicSuggest");
+    assert_eq!(suggest.return_ownership(), Ownership::Owned);
+
+    // `bool TWMnemonicIsValid(...)` returns a value.
+    assert_eq!(file_info.functions[0].return_ownership(), Ownership::None);
+
+    // Other pointers are borrowed, e.g. `const char*`.
+    let mut borrowed = suggest.return_type.clone();
+    borrowed.variant = TypeVariant::Char;
+    assert_eq!(borrowed.return_ownership(), Ownership::Borrowed);
+
+    // The convention can be overridden by the manifest.
+    file_info.functions[2].ownership = Some(Ownership::Borrowed);
+    let yaml = serde_yaml::to_string(&file_info).unwrap();
+    assert!(yaml.contains("ownership: borrowed"), "{yaml}");
+    let file_info = parse_str(&yaml).unwrap();
+    assert_eq!(
+        file_info.functions[2].return_ownership(),
+        Ownership::Borrowed
+    );
+}
+
+#[test]
+fn undocumented_method_names() {
+    let manifest = Manifest {
+        files: vec![
+            parse_str(include_str!("samples/undocumented.input.yaml")).unwrap(),
+            parse_str(include_str!("samples/comments.input.yaml")).unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    // Non-public functions are not checked.
+    assert_eq!(
+        undocumented_methods(&manifest),
+        ["TWFoo::TWFooVerify", "TWFoo::TWFooDescription"]
+    );
+}
+
+#[test]
+fn base_struct_composition() {
+    let file_info = parse_str(include_str!("samples/base_struct.input.yaml")).unwrap();
+    let [base, derived, linked] = &file_info.structs[..] else {
+        panic!("expected three structs");
+    };
+
+    // The base is embedded by value.
+    assert_eq!(
+        derived.fields[0].ty.variant,
+        TypeVariant::Struct("Base".to_string())
+    );
+    assert!(!derived.fields[0].ty.is_pointer());
+    assert_eq!(base_struct(derived), Some("Base".to_string()));
+
+    // Neither primitives nor pointers are bases.
+    assert_eq!(base_struct(base), None);
+    assert_eq!(base_struct(linked), None);
+}
+
+#[test]
+fn items_record_declaring_file() {
+    let (_, mnemonic) = parse_file("manifest/TWMnemonic.yaml").unwrap();
+    let (_, address) = parse_file("manifest/TWAnyAddress.yaml").unwrap();
+
+    let defined_in = |file: &FileInfo| {
+        file.functions
+            .iter()
+            .map(|func| func.defined_in.clone().unwrap())
+            .chain(file.structs.iter().map(|s| s.defined_in.clone().unwrap()))
+            .collect::<std::collections::BTreeSet<_>>()
+    };
+    assert_eq!(
+        defined_in(&mnemonic),
+        ["TWMnemonic.yaml".to_string()].into()
+    );
+    assert_eq!(
+        defined_in(&address),
+        ["TWAnyAddress.yaml".to_string()].into()
+    );
+
+    // Merged into a single file, the items can still be split by file.
+    let mut merged = mnemonic.clone();
+    merged.functions.extend(address.functions.clone());
+    let dir = std::env::temp_dir().join(format!("codegen-v2-defined-in-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("TWMerged.yaml");
+    std::fs::write(&path, serde_yaml::to_string(&merged).unwrap()).unwrap();
+
+    let (_, merged) = parse_file(&path).unwrap();
+    let last = merged.functions.last().unwrap();
+    assert_eq!(last.defined_in.as_deref(), Some("TWAnyAddress.yaml"));
+    assert_eq!(
+        merged.functions[0].defined_in.as_deref(),
+        Some("TWMnemonic.yaml")
+    );
+
+    // Strings have no declaring file.
+    let file_info = parse_str(include_str!("samples/struct_kinds.input.yaml")).unwrap();
+    assert_eq!(file_info.structs[0].defined_in, None);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -7,20 +7,35 @@
 use crate::codegen::swift::{render_to_strings, RenderIntput};
 use crate::manifest::parse_str;
 
+mod builder;
+mod codegen;
+mod csharp;
+mod diff;
+mod equatable;
+mod error;
+mod go;
+mod json_marshal;
+mod kotlin;
+mod manifest;
+mod objc;
+mod protobuf;
+mod python;
+mod rust;
+mod schema;
+mod typescript;
+mod visit;
+
 /// Convenience function.
-fn create_intput(yaml: &str) -> RenderIntput {
+fn create_intput(yaml: &str) -> RenderIntput<'static> {
     let file_info = parse_str(yaml).unwrap();
 
-    RenderIntput {
-        file_info,
-        struct_template: include_str!("../codegen/swift/templates/struct.hbs"),
-        enum_template: include_str!("../codegen/swift/templates/enum.hbs"),
-        extension_template: include_str!("../codegen/swift/templates/extension.hbs"),
-        proto_template: include_str!("../codegen/swift/templates/proto.hbs"),
-        partial_init_template: include_str!("../codegen/swift/templates/partial_init.hbs"),
-        partial_func_tempalte: include_str!("../codegen/swift/templates/partial_func.hbs"),
-        partial_prop_tempalte: include_str!("../codegen/swift/templates/partial_prop.hbs"),
-    }
+    RenderIntput::new(file_info)
+}
+
+// Convenience function: the samples carry the copyright year they were
+// generated in, hence we adjust it to the year the codegen stamps in.
+fn with_current_year(expected: &str) -> String {
+    expected.replace("2017-2023", &format!("2017-{}", crate::current_year()))
 }
 
 // Convenience function: runs the codegen on the given `input` and compares it
@@ -36,7 +51,7 @@ fn render_and_compare_struct(input: &str, expected: &str) {
 
     let (_name, output) = &rendered.structs[0];
     println!("{output}");
-    assert_eq!(output, expected);
+    assert_eq!(output, &with_current_year(expected));
 }
 
 fn render_and_compare_enum(input: &str, expected: &str) {
@@ -49,7 +64,7 @@ fn render_and_compare_enum(input: &str, expected: &str) {
     assert!(rendered.protos.is_empty());
 
     let (_name, output) = &rendered.enums[0];
-    assert_eq!(output, expected);
+    assert_eq!(output, &with_current_year(expected));
 }
 
 #[test]
@@ -116,11 +131,11 @@ fn enum_with_extension() {
 
     // Check generated enum.
     let (_name, output) = &rendered.enums[0];
-    assert_eq!(output, EXPECTED_ENUM);
+    assert_eq!(output, &with_current_year(EXPECTED_ENUM));
 
     // Check generated extension.
     let (_name, output) = &rendered.extensions[0];
-    assert_eq!(output, EXPECTED_EXTENSION);
+    assert_eq!(output, &with_current_year(EXPECTED_EXTENSION));
 }
 
 #[test]
@@ -130,3 +145,19 @@ fn non_associated() {
 
     render_and_compare_struct(INPUT, EXPECTED);
 }
+
+#[test]
+fn comments() {
+    const INPUT: &str = include_str!("samples/comments.input.yaml");
+    const EXPECTED: &str = include_str!("samples/comments.output.swift");
+
+    render_and_compare_struct(INPUT, EXPECTED);
+}
+
+#[test]
+fn deprecated() {
+    const INPUT: &str = include_str!("samples/deprecated.input.yaml");
+    const EXPECTED: &str = include_str!("samples/deprecated.output.swift");
+
+    render_and_compare_struct(INPUT, EXPECTED);
+}
@@ -0,0 +1,23 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::with_current_year;
+use crate::codegen::objc::generate_objc;
+use crate::manifest::parse_str;
+
+#[test]
+fn objc_bindings() {
+    const INPUT: &str = include_str!("samples/bindings.input.yaml");
+    const EXPECTED_HEADER: &str = include_str!("samples/bindings.output.h");
+    const EXPECTED_IMPLEMENTATION: &str = include_str!("samples/bindings.output.m");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let (header, implementation) = generate_objc(&file_info).unwrap();
+    println!("{header}");
+    println!("{implementation}");
+    assert_eq!(header, with_current_year(EXPECTED_HEADER));
+    assert_eq!(implementation, with_current_year(EXPECTED_IMPLEMENTATION));
+}
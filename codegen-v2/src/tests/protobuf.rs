@@ -0,0 +1,20 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::with_current_year;
+use crate::codegen::protobuf::generate_proto;
+use crate::manifest::parse_str;
+
+#[test]
+fn protobuf_schema() {
+    const INPUT: &str = include_str!("samples/protobuf.input.yaml");
+    const EXPECTED: &str = include_str!("samples/protobuf.output.proto");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let output = generate_proto(&file_info).unwrap();
+    println!("{output}");
+    assert_eq!(output, with_current_year(EXPECTED));
+}
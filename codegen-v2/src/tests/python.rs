@@ -0,0 +1,30 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::with_current_year;
+use crate::codegen::python::generate_python;
+use crate::manifest::parse_str;
+
+#[test]
+fn python_bindings() {
+    const INPUT: &str = include_str!("samples/bindings.input.yaml");
+    const EXPECTED: &str = include_str!("samples/bindings.output.py");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let output = generate_python(&file_info).unwrap();
+    println!("{output}");
+    assert_eq!(output, with_current_year(EXPECTED));
+}
+
+#[test]
+fn python_bitfields() {
+    let file_info = parse_str(include_str!("samples/bitfield.input.yaml")).unwrap();
+    let output = generate_python(&file_info).unwrap();
+
+    assert!(output.contains("(\"flag\", ctypes.c_uint32, 1),"));
+    assert!(output.contains("(\"kind\", ctypes.c_uint32, 7),"));
+    assert!(output.contains("(\"id\", ctypes.c_uint32),"));
+}
@@ -0,0 +1,72 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::with_current_year;
+use crate::codegen::rust::generate_rust;
+use crate::manifest::{parse_str, TypeVariant};
+use crate::Error;
+
+#[test]
+fn rust_bindings() {
+    const INPUT: &str = include_str!("samples/bindings.input.yaml");
+    const EXPECTED: &str = include_str!("samples/bindings.output.rs");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let output = generate_rust(&file_info).unwrap();
+    println!("{output}");
+    assert_eq!(output, with_current_year(EXPECTED));
+}
+
+#[test]
+fn rust_variadic_function() {
+    let file_info = parse_str(include_str!("samples/variadic.input.yaml")).unwrap();
+    let func = &file_info.functions[0];
+    assert!(func.is_variadic);
+    assert_eq!(func.params.len(), 1);
+
+    let output = generate_rust(&file_info).unwrap();
+    assert!(output.contains("pub fn TWLogWrite(fmt: *const c_char, ...);"));
+}
+
+#[test]
+fn rust_enum_backing_type() {
+    let file_info = parse_str(include_str!("samples/enum_backing.input.yaml")).unwrap();
+    assert_eq!(file_info.enums[0].value_type, TypeVariant::UInt64T);
+
+    let output = generate_rust(&file_info).unwrap();
+    assert!(output.contains("#[repr(u64)]"));
+
+    // C types are mapped to the primitive integers accepted by `repr`.
+    let mut file_info = file_info;
+    file_info.enums[0].value_type = TypeVariant::Int;
+    let output = generate_rust(&file_info).unwrap();
+    assert!(output.contains("#[repr(i32)]"), "{output}");
+
+    // The size of `long` depends on the target.
+    file_info.enums[0].value_type = TypeVariant::LongInt;
+    let err = generate_rust(&file_info).unwrap_err();
+    assert!(matches!(err, Error::BadType(_)), "{err:?}");
+}
+
+#[test]
+fn rust_void_pointer_return() {
+    let file_info = parse_str(include_str!("samples/void_return.input.yaml")).unwrap();
+    let output = generate_rust(&file_info).unwrap();
+
+    assert!(output.contains("pub fn TWFooReset(foo: *mut TWFoo);"));
+    assert!(output.contains("pub fn TWFooHandle(foo: *mut TWFoo) -> *mut c_void;"));
+}
+
+#[test]
+fn rust_rejects_bitfields() {
+    let file_info = parse_str(include_str!("samples/bitfield.input.yaml")).unwrap();
+
+    // Widening the bitfields to their full type would break the layout.
+    match generate_rust(&file_info).unwrap_err() {
+        Error::UnsupportedTarget(msg) => assert!(msg.contains("`flag` of TWFlags"), "{msg}"),
+        err => panic!("{err:?}"),
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+//
+// This is a GENERATED FILE, changes made here WILL BE LOST.
+//
+
+using System;
+using System.Runtime.InteropServices;
+
+namespace TrustWalletCore
+{
+    public enum TWMainEnum : uint
+    {
+        One = 0,
+        Two = 1,
+    }
+
+    public static class BindingsNative
+    {
+        private const string Library = "TrustWalletCore";
+
+        // Returns `IntPtr.Zero` if the result is null.
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        public static extern IntPtr TWMainStructCreateWithString(IntPtr @string);
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        public static extern void TWMainStructDelete(IntPtr obj);
+
+        // Returns `IntPtr.Zero` if the result is null.
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        public static extern IntPtr TWMainStructSign(IntPtr obj, IntPtr data, TWMainEnum kind);
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        [return: MarshalAs(UnmanagedType.I1)]
+        public static extern bool TWMainStructIsValid(ulong count);
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        public static extern void TWOtherStructReset();
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        public static extern int TWMainEnumIsSecond(TWMainEnum value);
+
+        [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+        public static extern IntPtr TWMainStructDescription(IntPtr obj);
+    }
+}
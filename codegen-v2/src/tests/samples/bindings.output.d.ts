@@ -0,0 +1,29 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+//
+// This is a GENERATED FILE, changes made here WILL BE LOST.
+//
+
+export enum MainEnum {
+    one = 0,
+    two = 1,
+}
+
+export class MainStruct {
+    static createWithString(string: string): MainStruct | null;
+    static isValid(count: bigint): boolean;
+    sign(data: Uint8Array, kind: MainEnum): string | null;
+    description(): string;
+    delete(): void;
+}
+
+export class OtherStruct {
+    static reset(): void;
+}
+
+export class MainEnumExt {
+    static isSecond(value: MainEnum): number;
+}
@@ -0,0 +1,71 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+//
+// This is a GENERATED FILE, changes made here WILL BE LOST.
+//
+
+package walletcore
+
+// #include <TrustWalletCore/Bindings.h>
+import "C"
+
+type MainEnum uint32
+
+const (
+	MainEnumOne MainEnum = 0
+	MainEnumTwo MainEnum = 1
+)
+
+type MainStruct struct {
+	ptr *C.struct_TWMainStruct
+}
+
+type OtherStruct struct {
+	ptr *C.struct_TWOtherStruct
+}
+
+// MainStructCreateWithString wraps `TWMainStructCreateWithString`.
+func MainStructCreateWithString(string string) *MainStruct {
+	stringTW := newTWString(string)
+	defer C.TWStringDelete(stringTW)
+	res := C.TWMainStructCreateWithString(stringTW)
+	if res == nil {
+		return nil
+	}
+	return &MainStruct{ptr: res}
+}
+
+// MainStructDelete wraps `TWMainStructDelete`.
+func MainStructDelete(obj *MainStruct) {
+	C.TWMainStructDelete(obj.ptr)
+}
+
+// MainStructSign wraps `TWMainStructSign`.
+func MainStructSign(obj *MainStruct, data []byte, kind MainEnum) string {
+	dataTW := newTWData(data)
+	defer C.TWDataDelete(dataTW)
+	return goString(C.TWMainStructSign(obj.ptr, dataTW, C.enum_TWMainEnum(kind)))
+}
+
+// MainStructIsValid wraps `TWMainStructIsValid`.
+func MainStructIsValid(count uint64) bool {
+	return bool(C.TWMainStructIsValid(C.uint64_t(count)))
+}
+
+// OtherStructReset wraps `TWOtherStructReset`.
+func OtherStructReset() {
+	C.TWOtherStructReset()
+}
+
+// MainEnumIsSecond wraps `TWMainEnumIsSecond`.
+func MainEnumIsSecond(value MainEnum) int32 {
+	return int32(C.TWMainEnumIsSecond(C.enum_TWMainEnum(value)))
+}
+
+// MainStructDescription wraps `TWMainStructDescription`.
+func MainStructDescription(obj *MainStruct) string {
+	return goString(C.TWMainStructDescription(obj.ptr))
+}
@@ -0,0 +1,45 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+//
+// This is a GENERATED FILE, changes made here WILL BE LOST.
+//
+
+#import <Foundation/Foundation.h>
+#import <TrustWalletCore/Bindings.h>
+
+NS_ASSUME_NONNULL_BEGIN
+
+@class MainStruct;
+@class OtherStruct;
+
+typedef NS_ENUM(uint32_t, MainEnum) {
+    MainEnumOne = 0,
+    MainEnumTwo = 1,
+};
+
+FOUNDATION_EXPORT int32_t MainEnumIsSecond(MainEnum value);
+
+@interface MainStruct : NSObject
+
+@property (nonatomic, readonly) struct TWMainStruct *rawValue;
+@property (nonatomic, readonly) NSString *description;
+
+- (instancetype)initWithRawValue:(struct TWMainStruct *)rawValue NS_DESIGNATED_INITIALIZER;
+- (instancetype)init NS_UNAVAILABLE;
+- (nullable instancetype)initWithString:(NSString *)string;
+
+- (nullable NSString *)sign:(NSData *)data kind:(MainEnum)kind;
++ (BOOL)isValid:(uint64_t)count;
+
+@end
+
+@interface OtherStruct : NSObject
+
++ (void)reset;
+
+@end
+
+NS_ASSUME_NONNULL_END
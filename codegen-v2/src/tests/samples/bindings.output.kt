@@ -0,0 +1,70 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+//
+// This is a GENERATED FILE, changes made here WILL BE LOST.
+//
+
+package com.trustwallet.core
+
+enum class MainEnum(
+    @get:JvmName("value")
+    val value: UInt,
+) {
+    One(0u),
+    Two(1u),
+    ;
+
+    @JvmName("isSecond")
+    external fun isSecond(): Int
+
+    companion object {
+        @JvmStatic
+        @JvmName("createFromValue")
+        fun fromValue(value: UInt): MainEnum? =
+            values().firstOrNull { it.value == value }
+    }
+}
+
+class MainStruct private constructor(
+    private val nativeHandle: Long,
+) {
+
+    init {
+        if (nativeHandle == 0L) throw IllegalArgumentException()
+    }
+
+    @Throws(IllegalArgumentException::class)
+    constructor(string: String) : this(createWithString(string))
+
+    val description: String
+        @JvmName("description")
+        external get
+
+    @JvmName("sign")
+    external fun sign(data: ByteArray, kind: MainEnum): String?
+
+    companion object {
+
+        @JvmStatic
+        @JvmName("isValid")
+        external fun isValid(count: ULong): Boolean
+
+        @JvmStatic
+        @JvmName("createFromNative")
+        private fun createFromNative(nativeHandle: Long) = MainStruct(nativeHandle)
+
+        @JvmStatic
+        @JvmName("createWithString")
+        private external fun createWithString(string: String): Long
+    }
+}
+
+object OtherStruct {
+
+    @JvmStatic
+    @JvmName("reset")
+    external fun reset()
+}
@@ -0,0 +1,73 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+//
+// This is a GENERATED FILE, changes made here WILL BE LOST.
+//
+
+#import "Bindings.h"
+
+int32_t MainEnumIsSecond(MainEnum value) {
+    int32_t result = TWMainEnumIsSecond((enum TWMainEnum)value);
+    return result;
+}
+
+@implementation MainStruct
+
+- (instancetype)initWithRawValue:(struct TWMainStruct *)rawValue {
+    self = [super init];
+    if (self) {
+        _rawValue = rawValue;
+    }
+    return self;
+}
+
+- (void)dealloc {
+    TWMainStructDelete(_rawValue);
+}
+
+- (nullable instancetype)initWithString:(NSString *)string {
+    TWString *stringTW = TWStringCreateWithUTF8Bytes([string UTF8String]);
+    struct TWMainStruct *result = TWMainStructCreateWithString(stringTW);
+    TWStringDelete(stringTW);
+    if (result == NULL) {
+        return nil;
+    }
+    return [self initWithRawValue:result];
+}
+
+- (NSString *)description {
+    TWString *result = TWMainStructDescription(self.rawValue);
+    NSString *output = [NSString stringWithUTF8String:TWStringUTF8Bytes(result)];
+    TWStringDelete(result);
+    return output;
+}
+
+- (nullable NSString *)sign:(NSData *)data kind:(MainEnum)kind {
+    TWData *dataTW = TWDataCreateWithBytes(data.bytes, data.length);
+    TWString *result = TWMainStructSign(self.rawValue, dataTW, (enum TWMainEnum)kind);
+    TWDataDelete(dataTW);
+    if (result == NULL) {
+        return nil;
+    }
+    NSString *output = [NSString stringWithUTF8String:TWStringUTF8Bytes(result)];
+    TWStringDelete(result);
+    return output;
+}
+
++ (BOOL)isValid:(uint64_t)count {
+    bool result = TWMainStructIsValid(count);
+    return result;
+}
+
+@end
+
+@implementation OtherStruct
+
++ (void)reset {
+    TWOtherStructReset();
+}
+
+@end
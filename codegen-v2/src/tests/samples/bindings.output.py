@@ -0,0 +1,67 @@
+# Copyright © 2017-2023 Trust Wallet.
+#
+# This file is part of Trust. The full Trust copyright notice, including
+# terms governing use, modification, and redistribution, is contained in the
+# file LICENSE at the root of the source code distribution tree.
+#
+# This is a GENERATED FILE, changes made here WILL BE LOST.
+#
+
+import ctypes
+from enum import IntEnum
+
+
+class TWMainEnum(IntEnum):
+    ONE = 0
+    TWO = 1
+
+
+_lib = None
+
+
+def load(path):
+    """Loads the native library and declares the signatures of its functions."""
+    global _lib
+    _lib = ctypes.CDLL(path)
+    _lib.TWMainStructCreateWithString.argtypes = [ctypes.c_void_p]
+    _lib.TWMainStructCreateWithString.restype = ctypes.c_void_p
+    _lib.TWMainStructDelete.argtypes = [ctypes.c_void_p]
+    _lib.TWMainStructDelete.restype = None
+    _lib.TWMainStructSign.argtypes = [ctypes.c_void_p, ctypes.c_void_p, ctypes.c_uint32]
+    _lib.TWMainStructSign.restype = ctypes.c_void_p
+    _lib.TWMainStructIsValid.argtypes = [ctypes.c_uint64]
+    _lib.TWMainStructIsValid.restype = ctypes.c_bool
+    _lib.TWOtherStructReset.argtypes = []
+    _lib.TWOtherStructReset.restype = None
+    _lib.TWMainEnumIsSecond.argtypes = [ctypes.c_uint32]
+    _lib.TWMainEnumIsSecond.restype = ctypes.c_int32
+    _lib.TWMainStructDescription.argtypes = [ctypes.c_void_p]
+    _lib.TWMainStructDescription.restype = ctypes.c_void_p
+
+
+def TWMainStructCreateWithString(string):
+    return _lib.TWMainStructCreateWithString(string)
+
+
+def TWMainStructDelete(obj):
+    return _lib.TWMainStructDelete(obj)
+
+
+def TWMainStructSign(obj, data, kind):
+    return _lib.TWMainStructSign(obj, data, kind)
+
+
+def TWMainStructIsValid(count):
+    return _lib.TWMainStructIsValid(count)
+
+
+def TWOtherStructReset():
+    return _lib.TWOtherStructReset()
+
+
+def TWMainEnumIsSecond(value):
+    return _lib.TWMainEnumIsSecond(value)
+
+
+def TWMainStructDescription(obj):
+    return _lib.TWMainStructDescription(obj)
@@ -0,0 +1,41 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+//
+// This is a GENERATED FILE, changes made here WILL BE LOST.
+//
+
+#![allow(non_camel_case_types, non_snake_case, unused_imports)]
+
+use super::*;
+use std::ffi::*;
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TWMainEnum {
+    One = 0,
+    Two = 1,
+}
+
+#[repr(C)]
+pub struct TWMainStruct {
+    _private: [u8; 0],
+}
+
+#[repr(C)]
+pub struct TWOtherStruct {
+    _private: [u8; 0],
+}
+
+#[link(name = "TrustWalletCore")]
+extern "C" {
+    pub fn TWMainStructCreateWithString(string: *const c_void) -> *mut TWMainStruct;
+    pub fn TWMainStructDelete(obj: *mut TWMainStruct);
+    pub fn TWMainStructSign(obj: *mut TWMainStruct, data: *const c_void, kind: TWMainEnum) -> *const c_void;
+    pub fn TWMainStructIsValid(count: u64) -> bool;
+    pub fn TWOtherStructReset();
+    pub fn TWMainEnumIsSecond(value: TWMainEnum) -> i32;
+    pub fn TWMainStructDescription(obj: *mut TWMainStruct) -> *const c_void;
+}
@@ -0,0 +1,28 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+//
+// This is a GENERATED FILE, changes made here WILL BE LOST.
+//
+
+import Foundation
+
+public struct MainStruct {
+    init() {}
+
+    /// Validates the mnemonic.
+    /// Returns true if it is valid.
+    public static func isValid() -> Bool {
+        let result = MainStructIsValid()
+        return result
+    }
+
+    /// Returns the first property.
+    public var firstProperty: Bool {
+        let obj = self.rawValue
+        let result = MainStructFirstProperty(obj)
+        return result
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+//
+// This is a GENERATED FILE, changes made here WILL BE LOST.
+//
+
+import Foundation
+
+@available(*, deprecated)
+public struct MainStruct {
+    init() {}
+
+    @available(*, deprecated, message: "Use \"secondFunction\" instead")
+    public static func firstFunction() -> Bool {
+        let result = MainStructFirstFunction()
+        return result
+    }
+
+    public static func secondFunction() -> Bool {
+        let result = MainStructSecondFunction()
+        return result
+    }
+
+}
@@ -0,0 +1,34 @@
+# Copyright © 2017-2023 Trust Wallet.
+#
+# This file is part of Trust. The full Trust copyright notice, including
+# terms governing use, modification, and redistribution, is contained in the
+# file LICENSE at the root of the source code distribution tree.
+#
+# This is a GENERATED FILE, changes made here WILL BE LOST.
+#
+
+import json
+
+from TWHandle import TWPoint
+
+
+def point_to_dict(value):
+    return {
+        "x": value.x,
+        "y": value.y,
+    }
+
+
+def point_from_dict(data):
+    return TWPoint(
+        x=data["x"],
+        y=data["y"],
+    )
+
+
+def point_to_json(value):
+    return json.dumps(point_to_dict(value))
+
+
+def point_from_json(text):
+    return point_from_dict(json.loads(text))
@@ -0,0 +1,14 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+//
+// This is a GENERATED FILE, changes made here WILL BE LOST.
+//
+
+export class Mnemonic {
+    static isValid(mnemonic: string): boolean;
+    static isValidWord(word: string): boolean;
+    static suggest(prefix: string): string;
+}
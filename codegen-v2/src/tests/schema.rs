@@ -0,0 +1,260 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::manifest::{parse_dir, parse_str};
+use crate::schema::manifest_json_schema;
+use serde_json::{json, Value};
+
+// The draft-07 keywords used by the manifest schema.
+const KEYWORDS: &[&str] = &[
+    "$schema",
+    "$ref",
+    "title",
+    "definitions",
+    "type",
+    "required",
+    "properties",
+    "items",
+    "minItems",
+    "maxItems",
+    "minimum",
+    "maximum",
+    "enum",
+    "anyOf",
+    "allOf",
+    "not",
+];
+
+// Checks that the schema only uses known keywords, well-typed, and that every
+// reference resolves.
+fn check_schema(schema: &Value, root: &Value) {
+    let Value::Object(map) = schema else {
+        panic!("expected a schema object, found: {schema}");
+    };
+
+    for (key, value) in map {
+        assert!(KEYWORDS.contains(&key.as_str()), "unknown keyword: {key}");
+
+        match key.as_str() {
+            "$ref" => {
+                let name = value
+                    .as_str()
+                    .and_then(|r| r.strip_prefix("#/definitions/"))
+                    .unwrap();
+                assert!(
+                    root["definitions"].get(name).is_some(),
+                    "unresolved: {name}"
+                );
+            }
+            "definitions" | "properties" => {
+                for schema in value.as_object().unwrap().values() {
+                    check_schema(schema, root);
+                }
+            }
+            "anyOf" | "allOf" => {
+                for schema in value.as_array().unwrap() {
+                    check_schema(schema, root);
+                }
+            }
+            "items" => match value {
+                Value::Array(schemas) => schemas.iter().for_each(|s| check_schema(s, root)),
+                schema => check_schema(schema, root),
+            },
+            "not" => check_schema(value, root),
+            "required" | "enum" => assert!(value.is_array()),
+            "type" => assert!(value.is_string() || value.is_array()),
+            "minItems" | "maxItems" | "minimum" | "maximum" => assert!(value.is_u64()),
+            _ => assert!(value.is_string()),
+        }
+    }
+}
+
+// A minimal validator, supporting the keywords of the manifest schema.
+fn is_valid(instance: &Value, schema: &Value, root: &Value) -> bool {
+    let map = schema.as_object().unwrap();
+
+    if let Some(name) = map.get("$ref").and_then(Value::as_str) {
+        let name = name.strip_prefix("#/definitions/").unwrap();
+        if !is_valid(instance, &root["definitions"][name], root) {
+            return false;
+        }
+    }
+
+    if let Some(ty) = map.get("type") {
+        let types = match ty {
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            ty => vec![ty.as_str().unwrap()],
+        };
+        let matches = types.iter().any(|ty| match *ty {
+            "object" => instance.is_object(),
+            "array" => instance.is_array(),
+            "string" => instance.is_string(),
+            "boolean" => instance.is_boolean(),
+            "integer" => instance.is_u64() || instance.is_i64(),
+            "null" => instance.is_null(),
+            _ => false,
+        });
+        if !matches {
+            return false;
+        }
+    }
+
+    if let (Some(required), Some(object)) = (map.get("required"), instance.as_object()) {
+        let has_all = required
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|key| object.contains_key(key.as_str().unwrap()));
+        if !has_all {
+            return false;
+        }
+    }
+
+    if let (Some(properties), Some(object)) = (map.get("properties"), instance.as_object()) {
+        for (key, schema) in properties.as_object().unwrap() {
+            if let Some(value) = object.get(key) {
+                if !is_valid(value, schema, root) {
+                    return false;
+                }
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (map.get("items"), instance.as_array()) {
+        let valid = match items {
+            Value::Array(schemas) => array
+                .iter()
+                .zip(schemas)
+                .all(|(item, schema)| is_valid(item, schema, root)),
+            schema => array.iter().all(|item| is_valid(item, schema, root)),
+        };
+        if !valid {
+            return false;
+        }
+    }
+
+    if let Some(array) = instance.as_array() {
+        let len = array.len() as u64;
+        if map.get("minItems").and_then(Value::as_u64).unwrap_or(0) > len
+            || map
+                .get("maxItems")
+                .and_then(Value::as_u64)
+                .unwrap_or(u64::MAX)
+                < len
+        {
+            return false;
+        }
+    }
+
+    if let Some(number) = instance.as_u64() {
+        if map.get("minimum").and_then(Value::as_u64).unwrap_or(0) > number
+            || map
+                .get("maximum")
+                .and_then(Value::as_u64)
+                .unwrap_or(u64::MAX)
+                < number
+        {
+            return false;
+        }
+    }
+
+    if let Some(values) = map.get("enum") {
+        if !values.as_array().unwrap().contains(instance) {
+            return false;
+        }
+    }
+
+    if let Some(schemas) = map.get("anyOf") {
+        if !schemas
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|s| is_valid(instance, s, root))
+        {
+            return false;
+        }
+    }
+
+    if let Some(schemas) = map.get("allOf") {
+        if !schemas
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|s| is_valid(instance, s, root))
+        {
+            return false;
+        }
+    }
+
+    if let Some(schema) = map.get("not") {
+        if is_valid(instance, schema, root) {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[test]
+fn manifest_schema_is_valid() {
+    let schema = manifest_json_schema();
+    assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+    check_schema(&schema, &schema);
+}
+
+#[test]
+fn manifest_files_match_schema() {
+    let schema = manifest_json_schema();
+
+    let mut files = parse_dir("manifest/").unwrap();
+    files.push(parse_str(include_str!("samples/bitfield.input.yaml")).unwrap());
+    files.push(parse_str(include_str!("samples/function_pointer.input.yaml")).unwrap());
+    files.push(parse_str(include_str!("samples/unknown_variant.input.yaml")).unwrap());
+    files.push(parse_str(include_str!("samples/enum_comments.input.yaml")).unwrap());
+
+    for file in files {
+        let instance = serde_json::to_value(&file).unwrap();
+        assert!(is_valid(&instance, &schema, &schema), "{}", file.name);
+    }
+
+    // The older `[name, type]` field pairs are valid, too.
+    let pair = json!({
+        "name": "TWFoo",
+        "structs": [{
+            "name": "TWFoo",
+            "is_public": true,
+            "is_class": false,
+            "fields": [["x", {
+                "variant": "int32_t",
+                "is_constant": false,
+                "is_nullable": false,
+                "pointer_depth": 0,
+            }]],
+        }],
+    });
+    assert!(is_valid(&pair, &schema, &schema));
+
+    // Missing required members and mistyped values are rejected.
+    assert!(!is_valid(&json!({ "structs": [] }), &schema, &schema));
+    let mistyped = json!({
+        "name": "TWFoo",
+        "enums": [{ "name": "TWBar", "is_public": "yes", "value_type": { "variant": "int" } }],
+    });
+    assert!(!is_valid(&mistyped, &schema, &schema));
+    let missing_value = json!({
+        "name": "TWFoo",
+        "typedefs": [{
+            "name": "TWBar",
+            "underlying": {
+                "variant": "struct",
+                "is_constant": false,
+                "is_nullable": false,
+                "pointer_depth": 0,
+            },
+        }],
+    });
+    assert!(!is_valid(&missing_value, &schema, &schema));
+}
@@ -0,0 +1,65 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use super::with_current_year;
+use crate::codegen::typescript::generate_dts;
+use crate::manifest::{build_manifest, parse_file, parse_str, Manifest};
+
+#[test]
+fn typescript_declarations() {
+    const INPUT: &str = include_str!("samples/bindings.input.yaml");
+    const EXPECTED: &str = include_str!("samples/bindings.output.d.ts");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let output = generate_dts(&file_info, &Manifest::default()).unwrap();
+    println!("{output}");
+    assert_eq!(output, with_current_year(EXPECTED));
+}
+
+#[test]
+fn typescript_declarations_mnemonic() {
+    const INPUT: &str = include_str!("../../manifest/TWMnemonic.yaml");
+    const EXPECTED: &str = include_str!("samples/mnemonic.output.d.ts");
+
+    let file_info = parse_str(INPUT).unwrap();
+    let output = generate_dts(&file_info, &Manifest::default()).unwrap();
+    println!("{output}");
+    assert_eq!(output, with_current_year(EXPECTED));
+}
+
+#[test]
+fn typescript_static_function_keeps_params() {
+    let (_, file_info) = parse_file("manifest/TWSegwitAddress.yaml").unwrap();
+    let output = generate_dts(&file_info, &Manifest::default()).unwrap();
+
+    // Only the implicit 'self' parameter of instance methods is skipped.
+    assert!(
+        output.contains("static equal(lhs: SegwitAddress, rhs: SegwitAddress): boolean;"),
+        "{output}"
+    );
+}
+
+#[test]
+fn typescript_imports_types_of_other_files() {
+    let manifest = build_manifest("manifest/").unwrap();
+    let file_info = manifest
+        .files
+        .iter()
+        .find(|file| file.name == "TWAnyAddress")
+        .unwrap();
+    let output = generate_dts(file_info, &manifest).unwrap();
+
+    assert!(
+        output.contains("import type { CoinType } from \"./TWCoinType\";"),
+        "{output}"
+    );
+    assert!(
+        output.contains("import type { PublicKey } from \"./TWPublicKey\";"),
+        "{output}"
+    );
+    // Types of the file itself are not imported.
+    assert!(!output.contains("\"./TWAnyAddress\""), "{output}");
+}
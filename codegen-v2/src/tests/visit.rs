@@ -0,0 +1,51 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::manifest::{
+    parse_str, EnumInfo, FileInfo, FunctionInfo, Manifest, PropertyInfo, StructInfo,
+};
+use crate::visit::{walk, ManifestVisitor};
+
+#[derive(Default)]
+struct CountingVisitor {
+    structs: usize,
+    enums: usize,
+    methods: usize,
+    properties: usize,
+}
+
+impl ManifestVisitor for CountingVisitor {
+    fn visit_struct(&mut self, _file: &FileInfo, _strct: &StructInfo) {
+        self.structs += 1;
+    }
+    fn visit_enum(&mut self, _file: &FileInfo, _enm: &EnumInfo) {
+        self.enums += 1;
+    }
+    fn visit_method(&mut self, _file: &FileInfo, _func: &FunctionInfo) {
+        self.methods += 1;
+    }
+    fn visit_property(&mut self, _file: &FileInfo, _prop: &PropertyInfo) {
+        self.properties += 1;
+    }
+}
+
+#[test]
+fn walk_counts_items() {
+    let file_info = parse_str(include_str!("samples/bindings.input.yaml")).unwrap();
+    let manifest = Manifest {
+        files: vec![file_info.clone(), file_info],
+        type_index: Default::default(),
+        file_paths: Default::default(),
+    };
+
+    let mut visitor = CountingVisitor::default();
+    walk(&manifest, &mut visitor);
+
+    assert_eq!(visitor.structs, 4);
+    assert_eq!(visitor.enums, 2);
+    assert_eq!(visitor.methods, 8);
+    assert_eq!(visitor.properties, 2);
+}
@@ -0,0 +1,37 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use crate::manifest::{EnumInfo, FileInfo, FunctionInfo, Manifest, PropertyInfo, StructInfo};
+
+/// Callbacks for the items of a manifest, driven by `walk`. Every callback
+/// receives the file declaring the item and does nothing by default, so
+/// generators only implement the ones they need. Non-public items are
+/// visited, too.
+pub trait ManifestVisitor {
+    fn visit_struct(&mut self, _file: &FileInfo, _strct: &StructInfo) {}
+    fn visit_enum(&mut self, _file: &FileInfo, _enm: &EnumInfo) {}
+    fn visit_method(&mut self, _file: &FileInfo, _func: &FunctionInfo) {}
+    fn visit_property(&mut self, _file: &FileInfo, _prop: &PropertyInfo) {}
+}
+
+/// Visits the structs, enums, methods and properties of every manifest file,
+/// in that order per file.
+pub fn walk(manifest: &Manifest, visitor: &mut impl ManifestVisitor) {
+    for file in &manifest.files {
+        for strct in &file.structs {
+            visitor.visit_struct(file, strct);
+        }
+        for enm in &file.enums {
+            visitor.visit_enum(file, enm);
+        }
+        for func in &file.functions {
+            visitor.visit_method(file, func);
+        }
+        for prop in &file.properties {
+            visitor.visit_property(file, prop);
+        }
+    }
+}
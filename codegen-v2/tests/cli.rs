@@ -0,0 +1,238 @@
+// Copyright © 2017-2023 Trust Wallet.
+//
+// This file is part of Trust. The full Trust copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+use libparser::manifest::{parse_dir_incremental, ManifestCache};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const PARSER: &str = env!("CARGO_BIN_EXE_parser");
+
+// Convenience function: creates an empty directory for the given test.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("codegen-v2-cli-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write_manifest(input: &Path, output: &Path, format: &str) -> std::process::Output {
+    Command::new(PARSER)
+        .args(["manifest", "--input"])
+        .arg(input)
+        .arg("--output")
+        .arg(output)
+        .args(["--format", format])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn manifest_command_writes_files() {
+    let dir = test_dir("manifest");
+    let (input, output) = (dir.join("input"), dir.join("output"));
+    std::fs::create_dir_all(&input).unwrap();
+
+    let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/tests/samples");
+    for name in ["class", "enum"] {
+        std::fs::copy(
+            samples.join(format!("{name}.input.yaml")),
+            input.join(format!("{name}.yaml")),
+        )
+        .unwrap();
+    }
+
+    let result = write_manifest(&input, &output, "yaml");
+    assert!(result.status.success());
+
+    let stdout = String::from_utf8(result.stdout).unwrap();
+    assert!(stdout.contains("Wrote 2 manifest files"));
+
+    // Files are named after the manifest name.
+    let mut written = std::fs::read_dir(&output)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    written.sort();
+    assert_eq!(written, vec!["Class.yaml", "Enum.yaml"]);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn manifest_command_fails_on_bad_input() {
+    let dir = test_dir("bad-input");
+    std::fs::write(dir.join("README.md"), "Not a manifest.").unwrap();
+
+    let result = write_manifest(&dir, &dir.join("output"), "json");
+    assert!(!result.status.success());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn manifest_command_include_filter() {
+    let dir = test_dir("include");
+    let (input, output) = (dir.join("input"), dir.join("output"));
+    std::fs::create_dir_all(&input).unwrap();
+
+    let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/tests/samples");
+    for name in ["class", "enum"] {
+        std::fs::copy(
+            samples.join(format!("{name}.input.yaml")),
+            input.join(format!("{name}.yaml")),
+        )
+        .unwrap();
+    }
+
+    let result = Command::new(PARSER)
+        .args(["manifest", "--input"])
+        .arg(&input)
+        .arg("--output")
+        .arg(&output)
+        .args(["--include", "enum*"])
+        .output()
+        .unwrap();
+    assert!(result.status.success());
+
+    let written = std::fs::read_dir(&output)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(written, vec!["Enum.json"]);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn generate_command_writes_bindings() {
+    let dir = test_dir("generate");
+    let (input, output) = (dir.join("input"), dir.join("output"));
+    std::fs::create_dir_all(&input).unwrap();
+
+    let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/tests/samples");
+    std::fs::copy(
+        samples.join("bindings.input.yaml"),
+        input.join("bindings.yaml"),
+    )
+    .unwrap();
+
+    let generate = |lang: &str| {
+        Command::new(PARSER)
+            .args(["generate", "--lang", lang, "--input"])
+            .arg(&input)
+            .arg(&output)
+            .output()
+            .unwrap()
+    };
+
+    assert!(generate("kotlin").status.success());
+    let written = std::fs::read_to_string(output.join("Bindings.kt")).unwrap();
+    assert!(written.contains("class MainStruct"));
+
+    // Targets with multiple modules also get an index.
+    assert!(generate("typescript").status.success());
+    let index = std::fs::read_to_string(output.join("index.d.ts")).unwrap();
+    assert!(index.contains("./Bindings"), "{index}");
+
+    // The cache is written for later runs, e.g. of `gen-file`.
+    let cache_path = dir.join("cache.json");
+    let result = Command::new(PARSER)
+        .args(["generate", "--lang", "kotlin", "--input"])
+        .arg(&input)
+        .arg("--cache")
+        .arg(&cache_path)
+        .arg(&output)
+        .output()
+        .unwrap();
+    assert!(result.status.success());
+    let cache = ManifestCache::load(&cache_path).unwrap();
+    assert_eq!(cache.entries.len(), 1);
+
+    // Objective-C and Swift render multiple files per manifest file.
+    assert!(generate("objc").status.success());
+    let header = std::fs::read_to_string(output.join("Bindings.h")).unwrap();
+    assert!(header.contains("@interface MainStruct"), "{header}");
+    assert!(output.join("Bindings.m").exists());
+    let umbrella = std::fs::read_to_string(output.join("WalletCore.h")).unwrap();
+    assert!(umbrella.contains("#import \"Bindings.h\""), "{umbrella}");
+
+    assert!(generate("swift").status.success());
+    assert!(output.join("MainStruct.swift").exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn manifest_command_writes_to_stdout() {
+    let dir = test_dir("stdout");
+    let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/tests/samples");
+    std::fs::copy(samples.join("enum.input.yaml"), dir.join("enum.yaml")).unwrap();
+
+    let result = write_manifest(&dir, Path::new("-"), "json");
+    assert!(result.status.success());
+
+    // A single file is written as the file info itself.
+    let stdout = String::from_utf8(result.stdout).unwrap();
+    let written: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(written["name"], "Enum");
+
+    // Multiple files are written as a list.
+    std::fs::copy(samples.join("class.input.yaml"), dir.join("class.yaml")).unwrap();
+    let result = write_manifest(&dir, Path::new("-"), "json");
+    let stdout = String::from_utf8(result.stdout).unwrap();
+    let written: Vec<serde_json::Value> = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(written.len(), 2);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn gen_file_command_generates_single_file() {
+    let dir = test_dir("gen-file");
+
+    let gen_file = |args: &[&str]| {
+        Command::new(PARSER)
+            .args(["gen-file", "manifest/TWAES.yaml"])
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    // Printed to stdout by default.
+    let result = gen_file(&["--lang", "kotlin"]);
+    assert!(result.status.success());
+    let stdout = String::from_utf8(result.stdout).unwrap();
+    assert!(stdout.contains("object AES"), "{stdout}");
+
+    // Swift renders multiple files per manifest file.
+    let out_dir = dir.join("swift");
+    let result = gen_file(&["--lang", "swift", "--out-dir", out_dir.to_str().unwrap()]);
+    assert!(result.status.success());
+    assert!(out_dir.join("AES.swift").exists());
+
+    // `TWAES` references `TWAESPaddingMode`, which must be declared by one of
+    // the cached files.
+    let cache_path = dir.join("cache.json");
+    let mut cache = ManifestCache::default();
+    parse_dir_incremental("manifest/", &mut cache).unwrap();
+    cache
+        .entries
+        .retain(|path, _| !path.ends_with("TWAESPaddingMode.yaml"));
+    cache.save(&cache_path).unwrap();
+
+    let cache_arg = ["--lang", "kotlin", "--cache", cache_path.to_str().unwrap()];
+    let result = gen_file(&cache_arg);
+    assert!(!result.status.success());
+    assert!(String::from_utf8(result.stderr)
+        .unwrap()
+        .contains("TWAESPaddingMode"));
+
+    parse_dir_incremental("manifest/", &mut cache).unwrap();
+    cache.save(&cache_path).unwrap();
+    assert!(gen_file(&cache_arg).status.success());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}